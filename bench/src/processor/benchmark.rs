@@ -1,6 +1,9 @@
 use {
     crate::cli::CliError,
-    clockwork_client::Client,
+    clockwork_client::{
+        queue::events::{parse_logs, QueueEvent},
+        Client,
+    },
     solana_client::{
         pubsub_client::PubsubClient,
         rpc_config::{RpcTransactionLogsConfig, RpcTransactionLogsFilter},
@@ -88,8 +91,8 @@ pub fn run(count: u32, parallelism: f32, _recurrence: u32) -> Result<(), CliErro
 
 fn _listen_for_events(
     num_expected_events: u32,
-    _expected_process_ats: &HashMap<Pubkey, Vec<i64>>,
-    _actual_process_ats: &mut HashMap<Pubkey, Vec<i64>>,
+    expected_process_ats: &HashMap<Pubkey, Vec<i64>>,
+    actual_process_ats: &mut HashMap<Pubkey, Vec<i64>>,
 ) -> Result<(), CliError> {
     let (ws_sub, log_receiver) = PubsubClient::logs_subscribe(
         "ws://localhost:8900/",
@@ -100,32 +103,23 @@ fn _listen_for_events(
     )
     .map_err(|_| CliError::WebsocketError)?;
 
-    // Watch for task exec events
-    let mut _event_count = 0;
+    // Watch for queue crank events
+    let mut event_count = 0;
     for log_response in log_receiver {
-        let logs = log_response.value.logs.into_iter();
-        for log in logs {
-            match &log[..14] {
-                "Program data: " => {
-                    // Decode event from log data
-                    // let mut buffer = Vec::<u8>::new();
-                    // base64::decode_config_buf(&log[14..], base64::STANDARD, &mut buffer).unwrap();
-                    // let event =
-                    //     borsh::try_from_slice_unchecked::<TaskExecuted>(&buffer[8..]).unwrap();
-                    // if expected_process_ats.contains_key(&event.queue) {
-                    //     actual_process_ats
-                    //         .entry(event.queue)
-                    //         .or_insert(Vec::new())
-                    //         .push(event.ts);
-                    //     event_count += 1;
-                    // }
+        for event in parse_logs(&log_response.value.logs) {
+            if let QueueEvent::Cranked(event) = event {
+                if expected_process_ats.contains_key(&event.queue) {
+                    actual_process_ats
+                        .entry(event.queue)
+                        .or_default()
+                        .push(event.unix_timestamp);
+                    event_count += 1;
                 }
-                _ => {}
             }
         }
 
         // Exit if we've received the expected number of events
-        if _event_count == num_expected_events {
+        if event_count == num_expected_events {
             break;
         }
     }
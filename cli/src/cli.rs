@@ -13,9 +13,27 @@ pub enum CliCommand {
 
     // Config commands
     ConfigGet,
+    ConfigMigrate,
     ConfigSet {
         admin: Option<Pubkey>,
         crank_fee: Option<u64>,
+        fee_mint: Option<Pubkey>,
+        max_lamports_per_signature: Option<u64>,
+        max_rate_limit: Option<u64>,
+        token_crank_fee: Option<u64>,
+    },
+
+    // Fee commands
+    FeeClaim {
+        amount: u64,
+        worker: Keypair,
+    },
+    FeeGet {
+        worker: Pubkey,
+    },
+    FeeWithholdingClaim {
+        amount: u64,
+        worker: Pubkey,
     },
 
     // Http
@@ -54,8 +72,18 @@ pub enum CliCommand {
     QueueGet {
         address: Pubkey,
     },
+    QueueMigrate {
+        address: Pubkey,
+    },
     QueueUpdate {
         address: Pubkey,
+        compute_unit_limit: Option<u32>,
+        compute_unit_price: Option<u64>,
+        exec_history_capacity: Option<u64>,
+        fee_bid: Option<u64>,
+        fee_mint: Option<Pubkey>,
+        max_retries: Option<u64>,
+        pool: Option<Pubkey>,
         rate_limit: Option<u64>,
     },
 
@@ -79,6 +107,10 @@ pub fn app() -> Command<'static> {
                 .about("Manage the Clockwork configs")
                 .arg_required_else_help(true)
                 .subcommand(Command::new("get").about("Get a config value"))
+                .subcommand(
+                    Command::new("migrate")
+                        .about("Reallocate the queue config to hold its latest fields"),
+                )
                 .subcommand(
                     Command::new("set")
                         .about("Set a config value")
@@ -94,25 +126,110 @@ pub fn app() -> Command<'static> {
                                 .value_name("NUM_LAMPORTS")
                                 .takes_value(true),
                         )
+                        .arg(
+                            Arg::new("fee_mint")
+                                .long("fee_mint")
+                                .value_name("PUBKEY")
+                                .takes_value(true),
+                        )
                         .arg(
                             Arg::new("grace_period")
                                 .long("grace_period")
                                 .value_name("NUM_SECONDS")
                                 .takes_value(true),
                         )
+                        .arg(
+                            Arg::new("max_lamports_per_signature")
+                                .long("max_lamports_per_signature")
+                                .value_name("NUM_LAMPORTS")
+                                .takes_value(true),
+                        )
+                        .arg(
+                            Arg::new("max_rate_limit")
+                                .long("max_rate_limit")
+                                .value_name("NUM_CRANKS")
+                                .takes_value(true),
+                        )
                         .arg(
                             Arg::new("spam_penalty")
                                 .long("spam_penalty")
                                 .value_name("NUM_LAMPORTS")
                                 .takes_value(true),
                         )
+                        .arg(
+                            Arg::new("token_crank_fee")
+                                .long("token_crank_fee")
+                                .value_name("NUM_TOKENS")
+                                .takes_value(true),
+                        )
                         .group(
                             ArgGroup::new("config_settings")
-                                .args(&["admin", "worker_fee", "grace_period", "spam_penalty"])
+                                .args(&[
+                                    "admin",
+                                    "worker_fee",
+                                    "fee_mint",
+                                    "grace_period",
+                                    "max_lamports_per_signature",
+                                    "max_rate_limit",
+                                    "spam_penalty",
+                                    "token_crank_fee",
+                                ])
                                 .multiple(true),
                         ),
                 ),
         )
+        .subcommand(
+            Command::new("fee")
+                .about("Manage worker fees")
+                .arg_required_else_help(true)
+                .subcommand(
+                    Command::new("claim")
+                        .about("Claim the fees escrowed to a worker")
+                        .arg(
+                            Arg::new("amount")
+                                .index(1)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The number of lamports to claim"),
+                        )
+                        .arg(
+                            Arg::new("worker")
+                                .index(2)
+                                .takes_value(true)
+                                .required(true)
+                                .help("Filepath to the worker keypair"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("get")
+                        .about("Lookup the fee account of a worker")
+                        .arg(
+                            Arg::new("worker_address")
+                                .index(1)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The worker address to lookup"),
+                        ),
+                )
+                .subcommand(
+                    Command::new("withholding")
+                        .about("Claim the fees withheld from a worker (admin only)")
+                        .arg(
+                            Arg::new("amount")
+                                .index(1)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The number of lamports to claim"),
+                        )
+                        .arg(
+                            Arg::new("worker_address")
+                                .index(2)
+                                .takes_value(true)
+                                .required(true)
+                                .help("The worker address to claim from"),
+                        ),
+                ),
+        )
         .subcommand(
             Command::new("initialize")
                 .about("Initialize the Clockwork programs")
@@ -200,9 +317,69 @@ pub fn app() -> Command<'static> {
                         .help("Public address of a queue"),
                 )
                 .subcommand(Command::new("get").about("Get a queue"))
+                .subcommand(
+                    Command::new("migrate")
+                        .about("Convert a queue from its original layout to the current one"),
+                )
                 .subcommand(
                     Command::new("update")
                         .about("Update a property on a queue")
+                        .arg(
+                            Arg::new("compute_unit_limit")
+                                .long("compute_unit_limit")
+                                .short('l')
+                                .takes_value(true)
+                                .required(false)
+                                .help("The compute unit limit of each crank transaction"),
+                        )
+                        .arg(
+                            Arg::new("compute_unit_price")
+                                .long("compute_unit_price")
+                                .short('p')
+                                .takes_value(true)
+                                .required(false)
+                                .help("The priority fee in micro-lamports per compute unit"),
+                        )
+                        .arg(
+                            Arg::new("exec_history_capacity")
+                                .long("exec_history_capacity")
+                                .short('e')
+                                .takes_value(true)
+                                .required(false)
+                                .help("The maximum number of execution records to retain"),
+                        )
+                        .arg(
+                            Arg::new("fee_bid")
+                                .long("fee_bid")
+                                .short('f')
+                                .takes_value(true)
+                                .required(false)
+                                .help("The fee to pay per crank"),
+                        )
+                        .arg(
+                            Arg::new("fee_mint")
+                                .long("fee_mint")
+                                .value_name("PUBKEY")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The whitelisted token mint to pay crank fees in"),
+                        )
+                        .arg(
+                            Arg::new("max_retries")
+                                .long("max_retries")
+                                .short('m')
+                                .takes_value(true)
+                                .required(false)
+                                .help("The number of times a failing crank may be retried"),
+                        )
+                        .arg(
+                            Arg::new("pool")
+                                .long("pool")
+                                .value_name("PUBKEY")
+                                .takes_value(true)
+                                .required(false)
+                                .help("The worker pool allowed to crank the queue"),
+                        )
                         .arg(
                             Arg::new("rate_limit")
                                 .long("rate_limit")
@@ -17,6 +17,7 @@ impl TryFrom<&ArgMatches> for CliCommand {
         match matches.subcommand() {
             Some(("api", matches)) => parse_api_command(matches),
             Some(("config", matches)) => parse_config_command(matches),
+            Some(("fee", matches)) => parse_fee_command(matches),
             Some(("http", matches)) => parse_http_command(matches),
             Some(("initialize", matches)) => parse_initialize_command(matches),
             Some(("localnet", matches)) => parse_bpf_command(matches),
@@ -83,9 +84,34 @@ fn parse_api_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
 fn parse_config_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     match matches.subcommand() {
         Some(("get", _)) => Ok(CliCommand::ConfigGet {}),
+        Some(("migrate", _)) => Ok(CliCommand::ConfigMigrate {}),
         Some(("set", matches)) => Ok(CliCommand::ConfigSet {
             admin: parse_pubkey("admin", matches).map_or(None, |v| Some(v)),
             crank_fee: parse_u64("crank_fee", matches).map_or(None, |v| Some(v)),
+            fee_mint: parse_pubkey("fee_mint", matches).map_or(None, |v| Some(v)),
+            max_lamports_per_signature: parse_u64("max_lamports_per_signature", matches)
+                .map_or(None, |v| Some(v)),
+            max_rate_limit: parse_u64("max_rate_limit", matches).map_or(None, |v| Some(v)),
+            token_crank_fee: parse_u64("token_crank_fee", matches).map_or(None, |v| Some(v)),
+        }),
+        _ => Err(CliError::CommandNotRecognized(
+            matches.subcommand().unwrap().0.into(),
+        )),
+    }
+}
+
+fn parse_fee_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
+    match matches.subcommand() {
+        Some(("claim", matches)) => Ok(CliCommand::FeeClaim {
+            amount: parse_u64("amount", matches)?,
+            worker: parse_keypair_file("worker", matches)?,
+        }),
+        Some(("get", matches)) => Ok(CliCommand::FeeGet {
+            worker: parse_pubkey("worker_address", matches)?,
+        }),
+        Some(("withholding", matches)) => Ok(CliCommand::FeeWithholdingClaim {
+            amount: parse_u64("amount", matches)?,
+            worker: parse_pubkey("worker_address", matches)?,
         }),
         _ => Err(CliError::CommandNotRecognized(
             matches.subcommand().unwrap().0.into(),
@@ -130,8 +156,16 @@ fn parse_queue_command(matches: &ArgMatches) -> Result<CliCommand, CliError> {
     let address = parse_pubkey("address", matches)?;
     match matches.subcommand() {
         Some(("get", _)) => Ok(CliCommand::QueueGet { address }),
+        Some(("migrate", _)) => Ok(CliCommand::QueueMigrate { address }),
         Some(("update", matches)) => Ok(CliCommand::QueueUpdate {
             address: address,
+            compute_unit_limit: parse_u32("compute_unit_limit", matches).ok(),
+            compute_unit_price: parse_u64("compute_unit_price", matches).ok(),
+            exec_history_capacity: parse_u64("exec_history_capacity", matches).ok(),
+            fee_bid: parse_u64("fee_bid", matches).ok(),
+            fee_mint: parse_pubkey("fee_mint", matches).ok(),
+            max_retries: parse_u64("max_retries", matches).ok(),
+            pool: parse_pubkey("pool", matches).ok(),
             rate_limit: parse_u64("rate_limit", matches).map_or(None, |v| Some(v)),
         }),
         _ => Err(CliError::CommandNotRecognized(
@@ -180,6 +214,13 @@ pub fn _parse_i64(arg: &str, matches: &ArgMatches) -> Result<i64, CliError> {
         .unwrap())
 }
 
+pub fn parse_u32(arg: &str, matches: &ArgMatches) -> Result<u32, CliError> {
+    Ok(parse_string(arg, matches)?
+        .parse::<u32>()
+        .map_err(|_err| CliError::BadParameter(arg.into()))
+        .unwrap())
+}
+
 pub fn parse_u64(arg: &str, matches: &ArgMatches) -> Result<u64, CliError> {
     Ok(parse_string(arg, matches)?
         .parse::<u64>()
@@ -1,10 +1,15 @@
 use {
     crate::errors::CliError,
     clockwork_client::{
-        network::objects::Config as NetworkConfig, pool::objects::Config as PoolConfig,
-        queue::objects::Config as CrankConfig, Client,
+        network::objects::Config as NetworkConfig,
+        pool::objects::Config as PoolConfig,
+        queue::objects::{Config as CrankConfig, FeeMint},
+        Client,
     },
     solana_sdk::pubkey::Pubkey,
+    spl_associated_token_account::{
+        get_associated_token_address, instruction::create_associated_token_account,
+    },
 };
 
 pub fn get(client: &Client) -> Result<(), CliError> {
@@ -34,7 +39,26 @@ pub fn get(client: &Client) -> Result<(), CliError> {
     Ok(())
 }
 
-pub fn set(client: &Client, admin: Option<Pubkey>, crank_fee: Option<u64>) -> Result<(), CliError> {
+pub fn migrate(client: &Client) -> Result<(), CliError> {
+    let ix = clockwork_client::queue::instruction::config_migrate(
+        client.payer_pubkey(),
+        CrankConfig::pubkey(),
+    );
+
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+
+    Ok(())
+}
+
+pub fn set(
+    client: &Client,
+    admin: Option<Pubkey>,
+    crank_fee: Option<u64>,
+    fee_mint: Option<Pubkey>,
+    max_lamports_per_signature: Option<u64>,
+    max_rate_limit: Option<u64>,
+    token_crank_fee: Option<u64>,
+) -> Result<(), CliError> {
     let config_pubkey = CrankConfig::pubkey();
     let config = client
         .get::<CrankConfig>(&config_pubkey)
@@ -49,16 +73,45 @@ pub fn set(client: &Client, admin: Option<Pubkey>, crank_fee: Option<u64>) -> Re
             Some(crank_fee) => crank_fee,
             None => config.crank_fee,
         },
+        fee_mint: fee_mint
+            .or(config.fee_mint.map(|f| f.mint))
+            .map(|mint| FeeMint {
+                crank_fee: token_crank_fee
+                    .or(config.fee_mint.map(|f| f.crank_fee))
+                    .unwrap_or(0),
+                mint,
+            }),
+        max_lamports_per_signature: match max_lamports_per_signature {
+            Some(max_lamports_per_signature) => max_lamports_per_signature,
+            None => config.max_lamports_per_signature,
+        },
+        max_rate_limit: match max_rate_limit {
+            Some(max_rate_limit) => max_rate_limit,
+            None => config.max_rate_limit,
+        },
         worker_pool: config.worker_pool,
     };
 
-    let ix = clockwork_client::queue::instruction::config_update(
+    // If a mint is whitelisted, create the config's token account to withhold token crank fees in.
+    let mut ixs = vec![];
+    if let Some(fee_mint) = settings.fee_mint {
+        let config_tokens_pubkey = get_associated_token_address(&config_pubkey, &fee_mint.mint);
+        if client.get_account(&config_tokens_pubkey).is_err() {
+            ixs.push(create_associated_token_account(
+                &client.payer_pubkey(),
+                &config_pubkey,
+                &fee_mint.mint,
+            ));
+        }
+    }
+
+    ixs.push(clockwork_client::queue::instruction::config_update(
         client.payer_pubkey(),
         config_pubkey,
         settings,
-    );
+    ));
 
-    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    client.send_and_confirm(&ixs, &[client.payer()]).unwrap();
 
     Ok(())
 }
@@ -0,0 +1,40 @@
+use {
+    crate::errors::CliError,
+    clockwork_client::{queue::objects::Fee, Client},
+    solana_sdk::{
+        pubkey::Pubkey,
+        signature::{Keypair, Signer},
+    },
+};
+
+pub fn get(client: &Client, worker: Pubkey) -> Result<(), CliError> {
+    let fee_pubkey = Fee::pubkey(worker);
+    let fee = client
+        .get::<Fee>(&fee_pubkey)
+        .map_err(|_err| CliError::AccountDataNotParsable(fee_pubkey.to_string()))?;
+    println!("Address: {}\n{:#?}", fee_pubkey, fee);
+    Ok(())
+}
+
+pub fn claim(client: &Client, amount: u64, worker: Keypair) -> Result<(), CliError> {
+    let ix = clockwork_client::queue::instruction::fee_claim(
+        worker.pubkey(),
+        amount,
+        client.payer_pubkey(),
+    );
+    client
+        .send_and_confirm(&[ix], &[client.payer(), &worker])
+        .unwrap();
+    Ok(())
+}
+
+pub fn withholding_claim(client: &Client, amount: u64, worker: Pubkey) -> Result<(), CliError> {
+    let ix = clockwork_client::queue::instruction::fee_withholding_claim(
+        client.payer_pubkey(),
+        amount,
+        client.payer_pubkey(),
+        worker,
+    );
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    Ok(())
+}
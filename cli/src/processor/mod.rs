@@ -1,5 +1,6 @@
 mod api;
 mod config;
+mod fee;
 mod initialize;
 mod localnet;
 mod node;
@@ -19,7 +19,28 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
             base_url,
         } => super::api::api_new(&client, ack_authority, base_url),
         CliCommand::ConfigGet => super::config::get(&client),
-        CliCommand::ConfigSet { admin, crank_fee } => super::config::set(&client, admin, crank_fee),
+        CliCommand::ConfigMigrate => super::config::migrate(&client),
+        CliCommand::ConfigSet {
+            admin,
+            crank_fee,
+            fee_mint,
+            max_lamports_per_signature,
+            max_rate_limit,
+            token_crank_fee,
+        } => super::config::set(
+            &client,
+            admin,
+            crank_fee,
+            fee_mint,
+            max_lamports_per_signature,
+            max_rate_limit,
+            token_crank_fee,
+        ),
+        CliCommand::FeeClaim { amount, worker } => super::fee::claim(&client, amount, worker),
+        CliCommand::FeeGet { worker } => super::fee::get(&client, worker),
+        CliCommand::FeeWithholdingClaim { amount, worker } => {
+            super::fee::withholding_claim(&client, amount, worker)
+        }
         CliCommand::HttpRequestNew {
             api,
             id,
@@ -33,10 +54,29 @@ pub fn process(matches: &ArgMatches) -> Result<(), CliError> {
         CliCommand::NodeStake { address, amount } => super::node::stake(&client, address, amount),
         CliCommand::PoolGet => super::pool::get(&client),
         CliCommand::QueueGet { address } => super::queue::get(&client, &address),
+        CliCommand::QueueMigrate { address } => super::queue::migrate(&client, &address),
         CliCommand::QueueUpdate {
             address,
+            compute_unit_limit,
+            compute_unit_price,
+            exec_history_capacity,
+            fee_bid,
+            fee_mint,
+            max_retries,
+            pool,
             rate_limit,
-        } => super::queue::update(&client, &address, rate_limit),
+        } => super::queue::update(
+            &client,
+            &address,
+            compute_unit_limit,
+            compute_unit_price,
+            exec_history_capacity,
+            fee_bid,
+            fee_mint,
+            max_retries,
+            pool,
+            rate_limit,
+        ),
         CliCommand::RegistryGet => super::registry::get(&client),
         CliCommand::SnapshotGet { entry_id } => super::snapshot::get(&client, entry_id),
     }
@@ -1,6 +1,9 @@
 use {
     crate::errors::CliError,
-    clockwork_client::{queue::objects::Queue, Client},
+    clockwork_client::{
+        queue::objects::{LegacyQueue, Queue, QueueSettings},
+        Client,
+    },
     solana_sdk::pubkey::Pubkey,
 };
 
@@ -10,16 +13,74 @@ pub fn get(client: &Client, address: &Pubkey) -> Result<(), CliError> {
         .map_err(|_err| CliError::AccountDataNotParsable(address.to_string()))?;
     println!("{:#?}", queue);
 
+    // Render the execution history, newest first
+    println!(
+        "\nExecution history ({}/{}):",
+        queue.exec_history.len(),
+        queue.exec_history_capacity
+    );
+    println!(
+        "{:>12} {:>12} {:>7} {:>12}  {:<44}  trigger_context",
+        "started_at", "ended_at", "cranks", "fees", "worker"
+    );
+    for exec_record in queue.exec_history.iter().rev() {
+        println!(
+            "{:>12} {:>12} {:>7} {:>12}  {:<44}  {:?}",
+            exec_record.started_at,
+            exec_record.ended_at,
+            exec_record.crank_count,
+            exec_record.fees_charged,
+            exec_record.worker.to_string(),
+            exec_record.trigger_context
+        );
+    }
+
     Ok(())
 }
 
-pub fn update(client: &Client, address: &Pubkey, rate_limit: Option<u64>) -> Result<(), CliError> {
-    let ix = clockwork_client::queue::instruction::queue_update(
+pub fn migrate(client: &Client, address: &Pubkey) -> Result<(), CliError> {
+    let data = client
+        .get_account_data(address)
+        .map_err(|_err| CliError::AccountNotFound(address.to_string()))?;
+    let legacy_queue = LegacyQueue::try_parse(&data)
+        .map_err(|_err| CliError::AccountDataNotParsable(address.to_string()))?;
+    let ix = clockwork_client::queue::instruction::queue_migrate(
         client.payer_pubkey(),
         *address,
-        None,
+        legacy_queue.watched_account(),
+    );
+    client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn update(
+    client: &Client,
+    address: &Pubkey,
+    compute_unit_limit: Option<u32>,
+    compute_unit_price: Option<u64>,
+    exec_history_capacity: Option<u64>,
+    fee_bid: Option<u64>,
+    fee_mint: Option<Pubkey>,
+    max_retries: Option<u64>,
+    pool: Option<Pubkey>,
+    rate_limit: Option<u64>,
+) -> Result<(), CliError> {
+    let settings = QueueSettings {
+        compute_unit_limit: compute_unit_limit.map(Some),
+        compute_unit_price: compute_unit_price.map(Some),
+        exec_history_capacity,
+        fee_bid: fee_bid.map(Some),
+        fee_mint: fee_mint.map(Some),
+        max_retries,
+        pool: pool.map(Some),
         rate_limit,
-        None,
+        ..QueueSettings::default()
+    };
+    let ix = clockwork_client::queue::instruction::queue_update(
+        client.payer_pubkey(),
+        *address,
+        settings,
     );
     client.send_and_confirm(&[ix], &[client.payer()]).unwrap();
     Ok(())
@@ -0,0 +1,339 @@
+use {
+    solana_sdk::{
+        hash::Hash,
+        instruction::{CompiledInstruction, Instruction},
+        message::{
+            v0::{Message, MessageAddressTableLookup},
+            MessageHeader,
+        },
+        pubkey::Pubkey,
+    },
+    std::convert::TryFrom,
+};
+
+pub use solana_address_lookup_table_program::{
+    instruction::{
+        close_lookup_table, create_lookup_table, deactivate_lookup_table, extend_lookup_table,
+        freeze_lookup_table,
+    },
+    state::AddressLookupTable,
+    ID,
+};
+
+/// An address lookup table and the addresses it holds.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AddressLookupTableAccount {
+    /// The addresses held by the lookup table.
+    pub addresses: Vec<Pubkey>,
+    /// The address of the lookup table account.
+    pub key: Pubkey,
+}
+
+/// Compiles a list of instructions into a v0 message. Accounts which are neither signers nor invoked
+/// programs are loaded from the first lookup table holding them. Returns none if the message references
+/// more accounts than can be indexed.
+pub fn compile_v0_message(
+    payer: &Pubkey,
+    instructions: &[Instruction],
+    address_lookup_table_accounts: &[AddressLookupTableAccount],
+    recent_blockhash: Hash,
+) -> Option<Message> {
+    // Collect the keys referenced by the instructions, in the order they first appear.
+    let mut keys = vec![CompiledKey {
+        is_invoked: false,
+        is_signer: true,
+        is_writable: true,
+        pubkey: *payer,
+    }];
+    for ix in instructions {
+        upsert_key(&mut keys, ix.program_id, true, false, false);
+        for acc in ix.accounts.iter() {
+            upsert_key(&mut keys, acc.pubkey, false, acc.is_signer, acc.is_writable);
+        }
+    }
+
+    // Load all the keys that can be loaded from the lookup tables.
+    let mut address_table_lookups = vec![];
+    let mut loaded_writable_keys = vec![];
+    let mut loaded_readonly_keys = vec![];
+    for address_lookup_table_account in address_lookup_table_accounts {
+        let mut lookup = MessageAddressTableLookup {
+            account_key: address_lookup_table_account.key,
+            writable_indexes: vec![],
+            readonly_indexes: vec![],
+        };
+        keys.retain(|key| {
+            if key.is_invoked || key.is_signer {
+                return true;
+            }
+            let index = address_lookup_table_account
+                .addresses
+                .iter()
+                .position(|address| address.eq(&key.pubkey))
+                .and_then(|index| u8::try_from(index).ok());
+            match index {
+                None => true,
+                Some(index) => {
+                    if key.is_writable {
+                        lookup.writable_indexes.push(index);
+                        loaded_writable_keys.push(key.pubkey);
+                    } else {
+                        lookup.readonly_indexes.push(index);
+                        loaded_readonly_keys.push(key.pubkey);
+                    }
+                    false
+                }
+            }
+        });
+        if !lookup.writable_indexes.is_empty() || !lookup.readonly_indexes.is_empty() {
+            address_table_lookups.push(lookup);
+        }
+    }
+
+    // Order the static keys as writable signers, readonly signers, writable non-signers, and then readonly non-signers.
+    keys.sort_by_key(|key| (!key.is_signer, !key.is_writable));
+    let header = MessageHeader {
+        num_required_signatures: u8::try_from(keys.iter().filter(|key| key.is_signer).count())
+            .ok()?,
+        num_readonly_signed_accounts: u8::try_from(
+            keys.iter()
+                .filter(|key| key.is_signer && !key.is_writable)
+                .count(),
+        )
+        .ok()?,
+        num_readonly_unsigned_accounts: u8::try_from(
+            keys.iter()
+                .filter(|key| !key.is_signer && !key.is_writable)
+                .count(),
+        )
+        .ok()?,
+    };
+    let account_keys = keys.iter().map(|key| key.pubkey).collect::<Vec<Pubkey>>();
+
+    // Compile the instructions against the static keys followed by the loaded writable and readonly keys.
+    let all_keys = [
+        account_keys.clone(),
+        loaded_writable_keys,
+        loaded_readonly_keys,
+    ]
+    .concat();
+    let key_index = |pubkey: &Pubkey| {
+        all_keys
+            .iter()
+            .position(|key| key.eq(pubkey))
+            .and_then(|index| u8::try_from(index).ok())
+    };
+    let instructions = instructions
+        .iter()
+        .map(|ix| {
+            Some(CompiledInstruction {
+                program_id_index: key_index(&ix.program_id)?,
+                accounts: ix
+                    .accounts
+                    .iter()
+                    .map(|acc| key_index(&acc.pubkey))
+                    .collect::<Option<Vec<u8>>>()?,
+                data: ix.data.clone(),
+            })
+        })
+        .collect::<Option<Vec<CompiledInstruction>>>()?;
+
+    Some(Message {
+        header,
+        account_keys,
+        recent_blockhash,
+        instructions,
+        address_table_lookups,
+    })
+}
+
+/// A key referenced by a message, with its privileges merged across all of its uses.
+struct CompiledKey {
+    is_invoked: bool,
+    is_signer: bool,
+    is_writable: bool,
+    pubkey: Pubkey,
+}
+
+fn upsert_key(
+    keys: &mut Vec<CompiledKey>,
+    pubkey: Pubkey,
+    is_invoked: bool,
+    is_signer: bool,
+    is_writable: bool,
+) {
+    match keys.iter_mut().find(|key| key.pubkey.eq(&pubkey)) {
+        None => keys.push(CompiledKey {
+            is_invoked,
+            is_signer,
+            is_writable,
+            pubkey,
+        }),
+        Some(key) => {
+            key.is_invoked |= is_invoked;
+            key.is_signer |= is_signer;
+            key.is_writable |= is_writable;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use {super::*, solana_sdk::instruction::AccountMeta};
+
+    fn lookup_table(addresses: Vec<Pubkey>) -> AddressLookupTableAccount {
+        AddressLookupTableAccount {
+            addresses,
+            key: Pubkey::new_unique(),
+        }
+    }
+
+    #[test]
+    fn test_compile_v0_message_without_lookup_tables() {
+        let payer = Pubkey::new_unique();
+        let program_a = Pubkey::new_unique();
+        let program_b = Pubkey::new_unique();
+        let account_a = Pubkey::new_unique();
+        let account_b = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let instructions = vec![
+            Instruction::new_with_bytes(
+                program_a,
+                &[1],
+                vec![
+                    AccountMeta::new_readonly(account_a, false),
+                    AccountMeta::new_readonly(signer, true),
+                    AccountMeta::new(account_b, false),
+                ],
+            ),
+            Instruction::new_with_bytes(
+                program_b,
+                &[2],
+                vec![
+                    AccountMeta::new(account_a, false),
+                    AccountMeta::new_readonly(account_b, false),
+                ],
+            ),
+        ];
+        let message = compile_v0_message(&payer, &instructions, &[], Hash::default()).unwrap();
+
+        // Privileges are merged across uses, and keys are ordered by privilege and then by first appearance.
+        assert_eq!(
+            message.header,
+            MessageHeader {
+                num_required_signatures: 2,
+                num_readonly_signed_accounts: 1,
+                num_readonly_unsigned_accounts: 2,
+            }
+        );
+        assert_eq!(
+            message.account_keys,
+            vec![payer, signer, account_a, account_b, program_a, program_b]
+        );
+        assert_eq!(
+            message.instructions,
+            vec![
+                CompiledInstruction::new_from_raw_parts(4, vec![1], vec![2, 1, 3]),
+                CompiledInstruction::new_from_raw_parts(5, vec![2], vec![2, 3]),
+            ]
+        );
+        assert!(message.address_table_lookups.is_empty());
+    }
+
+    #[test]
+    fn test_compile_v0_message_with_lookup_tables() {
+        let payer = Pubkey::new_unique();
+        let program = Pubkey::new_unique();
+        let account_a = Pubkey::new_unique();
+        let account_b = Pubkey::new_unique();
+        let account_c = Pubkey::new_unique();
+        let account_d = Pubkey::new_unique();
+        let signer = Pubkey::new_unique();
+        let instructions = vec![Instruction::new_with_bytes(
+            program,
+            &[],
+            vec![
+                AccountMeta::new(account_a, false),
+                AccountMeta::new_readonly(account_b, false),
+                AccountMeta::new_readonly(account_c, false),
+                AccountMeta::new(signer, true),
+                AccountMeta::new_readonly(account_d, false),
+            ],
+        )];
+
+        // Signers and invoked programs are never loaded, and keys are loaded from the first table holding them.
+        let table_a = lookup_table(vec![
+            Pubkey::new_unique(),
+            account_a,
+            account_b,
+            program,
+            signer,
+        ]);
+        let table_b = lookup_table(vec![account_c, account_a]);
+        let table_c = lookup_table(vec![Pubkey::new_unique()]);
+        let message = compile_v0_message(
+            &payer,
+            &instructions,
+            &[table_a.clone(), table_b.clone(), table_c],
+            Hash::default(),
+        )
+        .unwrap();
+        assert_eq!(
+            message.header,
+            MessageHeader {
+                num_required_signatures: 2,
+                num_readonly_signed_accounts: 0,
+                num_readonly_unsigned_accounts: 2,
+            }
+        );
+        assert_eq!(
+            message.account_keys,
+            vec![payer, signer, program, account_d]
+        );
+
+        // Tables which load no keys are omitted.
+        assert_eq!(
+            message.address_table_lookups,
+            vec![
+                MessageAddressTableLookup {
+                    account_key: table_a.key,
+                    writable_indexes: vec![1],
+                    readonly_indexes: vec![2],
+                },
+                MessageAddressTableLookup {
+                    account_key: table_b.key,
+                    writable_indexes: vec![],
+                    readonly_indexes: vec![0],
+                },
+            ]
+        );
+
+        // Loaded keys are indexed after the static keys, writable before readonly.
+        assert_eq!(
+            message.instructions,
+            vec![CompiledInstruction::new_from_raw_parts(
+                2,
+                vec![],
+                vec![4, 5, 6, 1, 3]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_compile_v0_message_too_many_accounts() {
+        let payer = Pubkey::new_unique();
+        let accounts = (0..256)
+            .map(|_| AccountMeta::new_readonly(Pubkey::new_unique(), false))
+            .collect::<Vec<AccountMeta>>();
+        let instructions = vec![Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[],
+            accounts.clone(),
+        )];
+        assert!(compile_v0_message(&payer, &instructions, &[], Hash::default()).is_none());
+
+        // Accounts loaded from a lookup table do not count towards the static keys, but are still indexed.
+        let table = lookup_table(accounts.iter().map(|acc| acc.pubkey).collect());
+        assert!(compile_v0_message(&payer, &instructions, &[table], Hash::default()).is_none());
+    }
+}
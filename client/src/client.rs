@@ -1,3 +1,6 @@
+use crate::address_lookup_table::{
+    compile_v0_message, AddressLookupTable, AddressLookupTableAccount,
+};
 use anchor_lang::{
     prelude::{AccountMeta, Clock},
     AccountDeserialize,
@@ -6,19 +9,28 @@ use anchor_spl::token::{
     spl_token::{self, state::Account as TokenAccount},
     Mint,
 };
-use solana_client::{client_error, rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig};
+use serde_json::json;
+use solana_client::{
+    client_error,
+    rpc_client::RpcClient,
+    rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig},
+    rpc_request::RpcRequest,
+    rpc_response::{Response, RpcSimulateTransactionResult},
+};
 use solana_sdk::{
     commitment_config::CommitmentConfig,
     hash::Hash,
     instruction::Instruction,
+    message::VersionedMessage,
     program_error::ProgramError,
     program_pack::Pack,
     pubkey::Pubkey,
     signature::{Keypair, Signature, Signer},
     signers::Signers,
     system_instruction,
-    transaction::Transaction,
+    transaction::{Transaction, VersionedTransaction},
 };
+use solana_transaction_status::UiTransactionEncoding;
 use std::{
     fmt::Debug,
     ops::{Deref, DerefMut},
@@ -36,6 +48,12 @@ pub enum ClientError {
 
     #[error("Failed to deserialize account data")]
     DeserializationError,
+
+    #[error("Failed to compile the message")]
+    MessageCompilationError,
+
+    #[error("Failed to serialize transaction")]
+    SerializationError,
 }
 
 pub type ClientResult<T> = Result<T, ClientError>;
@@ -57,6 +75,19 @@ impl Client {
             .map_err(|_| ClientError::DeserializationError)?)
     }
 
+    pub fn get_address_lookup_table(
+        &self,
+        pubkey: &Pubkey,
+    ) -> ClientResult<AddressLookupTableAccount> {
+        let data = self.client.get_account_data(pubkey)?;
+        let address_lookup_table = AddressLookupTable::deserialize(&data)
+            .map_err(|_| ClientError::DeserializationError)?;
+        Ok(AddressLookupTableAccount {
+            addresses: address_lookup_table.addresses.to_vec(),
+            key: *pubkey,
+        })
+    }
+
     pub fn get_clock(&self) -> ClientResult<Clock> {
         let clock_pubkey = Pubkey::from_str("SysvarC1ock11111111111111111111111111111111").unwrap();
         let clock_data = self.client.get_account_data(&clock_pubkey)?;
@@ -109,6 +140,58 @@ impl Client {
         tx.sign(signers, self.latest_blockhash()?);
         Ok(self.send_and_confirm_transaction(&tx)?)
     }
+
+    pub fn send_versioned<T: Signers>(
+        &self,
+        ixs: &[Instruction],
+        signers: &T,
+        address_lookup_table_accounts: &[AddressLookupTableAccount],
+    ) -> ClientResult<Signature> {
+        let message = compile_v0_message(
+            &self.payer_pubkey(),
+            ixs,
+            address_lookup_table_accounts,
+            self.latest_blockhash()?,
+        )
+        .ok_or(ClientError::MessageCompilationError)?;
+        let tx = VersionedTransaction::try_new(VersionedMessage::V0(message), signers).map_err(
+            |err| client_error::ClientError::from(client_error::ClientErrorKind::from(err)),
+        )?;
+        self.send_versioned_transaction(&tx)
+    }
+
+    pub fn send_versioned_transaction(&self, tx: &VersionedTransaction) -> ClientResult<Signature> {
+        let config = RpcSendTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            preflight_commitment: Some(self.commitment().commitment),
+            ..RpcSendTransactionConfig::default()
+        };
+        let signature = self.client.send::<String>(
+            RpcRequest::SendTransaction,
+            json!([serialize_and_encode(tx)?, config]),
+        )?;
+        Signature::from_str(&signature).map_err(|_| ClientError::DeserializationError)
+    }
+
+    pub fn simulate_versioned_transaction_with_config(
+        &self,
+        tx: &VersionedTransaction,
+        config: RpcSimulateTransactionConfig,
+    ) -> ClientResult<Response<RpcSimulateTransactionResult>> {
+        let config = RpcSimulateTransactionConfig {
+            encoding: Some(UiTransactionEncoding::Base64),
+            ..config
+        };
+        Ok(self.client.send::<Response<RpcSimulateTransactionResult>>(
+            RpcRequest::SimulateTransaction,
+            json!([serialize_and_encode(tx)?, config]),
+        )?)
+    }
+}
+
+fn serialize_and_encode(tx: &VersionedTransaction) -> ClientResult<String> {
+    let serialized = bincode::serialize(tx).map_err(|_| ClientError::SerializationError)?;
+    Ok(base64::encode(serialized))
 }
 
 impl Debug for Client {
@@ -1,3 +1,4 @@
+pub mod address_lookup_table;
 pub mod network;
 pub mod pool;
 pub mod queue;
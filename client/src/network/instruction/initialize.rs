@@ -21,6 +21,7 @@ pub fn initialize(admin: Pubkey, mint: Pubkey) -> Instruction {
         accounts: vec![
             AccountMeta::new(admin, true),
             AccountMeta::new(authority_pubkey, false),
+            AccountMeta::new_readonly(clockwork_queue_program::objects::Config::pubkey(), false),
             AccountMeta::new_readonly(clockwork_queue_program::ID, false),
             AccountMeta::new(config_pubkey, false),
             AccountMeta::new(rotator_pubkey, false),
@@ -0,0 +1,313 @@
+use {
+    anchor_lang::{AnchorDeserialize, Discriminator},
+    clockwork_queue_program::ID,
+    solana_sdk::pubkey::Pubkey,
+    std::str::FromStr,
+};
+
+pub use clockwork_queue_program::events::*;
+
+const PROGRAM_DATA: &str = "Program data: ";
+
+/// An event emitted by the queue program.
+#[derive(Debug)]
+pub enum QueueEvent {
+    BalanceLow(QueueBalanceLow),
+    Cranked(QueueCranked),
+    Created(QueueCreated),
+    Deleted(QueueDeleted),
+    Failed(QueueFailed),
+    Funded(QueueFunded),
+    FundingReclaimed(FundingReclaimed),
+    Paused(QueuePaused),
+    Resumed(QueueResumed),
+    TokensWithdrawn(QueueTokensWithdrawn),
+    Updated(QueueUpdated),
+    Withdrawn(QueueWithdrawn),
+}
+
+impl QueueEvent {
+    /// Decodes an event from its serialized data (discriminator included).
+    pub fn try_from_data(data: &[u8]) -> Option<Self> {
+        if data.len() < 8 {
+            return None;
+        }
+        let (discriminator, mut data) = data.split_at(8);
+        let discriminator: [u8; 8] = discriminator.try_into().ok()?;
+        match discriminator {
+            d if d == QueueBalanceLow::discriminator() => QueueBalanceLow::deserialize(&mut data)
+                .ok()
+                .map(Self::BalanceLow),
+            d if d == QueueCranked::discriminator() => {
+                QueueCranked::deserialize(&mut data).ok().map(Self::Cranked)
+            }
+            d if d == QueueCreated::discriminator() => {
+                QueueCreated::deserialize(&mut data).ok().map(Self::Created)
+            }
+            d if d == QueueDeleted::discriminator() => {
+                QueueDeleted::deserialize(&mut data).ok().map(Self::Deleted)
+            }
+            d if d == QueueFailed::discriminator() => {
+                QueueFailed::deserialize(&mut data).ok().map(Self::Failed)
+            }
+            d if d == QueueFunded::discriminator() => {
+                QueueFunded::deserialize(&mut data).ok().map(Self::Funded)
+            }
+            d if d == FundingReclaimed::discriminator() => FundingReclaimed::deserialize(&mut data)
+                .ok()
+                .map(Self::FundingReclaimed),
+            d if d == QueuePaused::discriminator() => {
+                QueuePaused::deserialize(&mut data).ok().map(Self::Paused)
+            }
+            d if d == QueueResumed::discriminator() => {
+                QueueResumed::deserialize(&mut data).ok().map(Self::Resumed)
+            }
+            d if d == QueueTokensWithdrawn::discriminator() => {
+                QueueTokensWithdrawn::deserialize(&mut data)
+                    .ok()
+                    .map(Self::TokensWithdrawn)
+            }
+            d if d == QueueUpdated::discriminator() => {
+                QueueUpdated::deserialize(&mut data).ok().map(Self::Updated)
+            }
+            d if d == QueueWithdrawn::discriminator() => QueueWithdrawn::deserialize(&mut data)
+                .ok()
+                .map(Self::Withdrawn),
+            _ => None,
+        }
+    }
+}
+
+/// Parses the queue program events out of a transaction's log messages.
+///
+/// Only data logged while the queue program is the currently executing program is decoded,
+/// so events emitted by programs the queue invokes (or which invoke the queue) are ignored.
+pub fn parse_logs(logs: &[String]) -> Vec<QueueEvent> {
+    let mut events = vec![];
+    let mut program_stack: Vec<Pubkey> = vec![];
+    for log in logs {
+        if let Some(data) = log.strip_prefix(PROGRAM_DATA) {
+            if program_stack.last() != Some(&ID) {
+                continue;
+            }
+            if let Some(event) = base64::decode(data)
+                .ok()
+                .and_then(|data| QueueEvent::try_from_data(&data))
+            {
+                events.push(event);
+            }
+            continue;
+        }
+
+        // Track the program invocation stack.
+        let mut words = log.split_whitespace();
+        if words.next() != Some("Program") {
+            continue;
+        }
+        let program_id = match words.next().and_then(|w| Pubkey::from_str(w).ok()) {
+            None => continue,
+            Some(program_id) => program_id,
+        };
+        match words.next() {
+            Some("invoke") => program_stack.push(program_id),
+            Some("success") | Some("failed:") => {
+                program_stack.pop();
+            }
+            _ => {}
+        }
+    }
+    events
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        anchor_lang::Event,
+        clockwork_queue_program::objects::{Trigger, TriggerContext},
+    };
+
+    fn invoke(program_id: Pubkey, depth: usize) -> String {
+        format!("Program {} invoke [{}]", program_id, depth)
+    }
+
+    fn program_data(event: &impl Event) -> String {
+        format!("{}{}", PROGRAM_DATA, base64::encode(event.data()))
+    }
+
+    fn success(program_id: Pubkey) -> String {
+        format!("Program {} success", program_id)
+    }
+
+    fn round_trip(event: &impl Event) -> QueueEvent {
+        let logs = vec![invoke(ID, 1), program_data(event), success(ID)];
+        let mut events = parse_logs(&logs);
+        assert_eq!(events.len(), 1);
+        events.remove(0)
+    }
+
+    #[test]
+    fn test_round_trip() {
+        let queue = Pubkey::new_unique();
+        let worker = Pubkey::new_unique();
+
+        match round_trip(&QueueCranked {
+            fees_paid: 1_000,
+            queue,
+            slot: 42,
+            thread_complete: true,
+            trigger_context: TriggerContext::Immediate,
+            unix_timestamp: 1_660_000_000,
+            worker,
+        }) {
+            QueueEvent::Cranked(event) => {
+                assert_eq!(event.fees_paid, 1_000);
+                assert_eq!(event.queue, queue);
+                assert_eq!(event.slot, 42);
+                assert!(event.thread_complete);
+                assert_eq!(event.unix_timestamp, 1_660_000_000);
+                assert_eq!(event.worker, worker);
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&QueueCreated {
+            authority: worker,
+            id: "id".into(),
+            queue,
+            trigger: Trigger::Immediate,
+        }) {
+            QueueEvent::Created(event) => {
+                assert_eq!(event.authority, worker);
+                assert_eq!(event.id, "id");
+                assert_eq!(event.queue, queue);
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&QueueDeleted {
+            close_to: worker,
+            queue,
+        }) {
+            QueueEvent::Deleted(event) => {
+                assert_eq!(event.close_to, worker);
+                assert_eq!(event.queue, queue);
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&QueueFailed {
+            consecutive_failures: 2,
+            paused: false,
+            queue,
+            retry_at: Some(100),
+            worker,
+        }) {
+            QueueEvent::Failed(event) => {
+                assert_eq!(event.consecutive_failures, 2);
+                assert!(!event.paused);
+                assert_eq!(event.retry_at, Some(100));
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&QueuePaused { queue }) {
+            QueueEvent::Paused(event) => assert_eq!(event.queue, queue),
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&QueueResumed { queue }) {
+            QueueEvent::Resumed(event) => assert_eq!(event.queue, queue),
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&QueueUpdated {
+            queue,
+            rate_limit: 3,
+            trigger: Trigger::Immediate,
+        }) {
+            QueueEvent::Updated(event) => assert_eq!(event.rate_limit, 3),
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&QueueWithdrawn {
+            amount: 500,
+            pay_to: worker,
+            queue,
+        }) {
+            QueueEvent::Withdrawn(event) => {
+                assert_eq!(event.amount, 500);
+                assert_eq!(event.pay_to, worker);
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&QueueBalanceLow {
+            balance: 10,
+            queue,
+            threshold: 20,
+        }) {
+            QueueEvent::BalanceLow(event) => {
+                assert_eq!(event.balance, 10);
+                assert_eq!(event.threshold, 20);
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&QueueFunded {
+            amount: 700,
+            queue,
+            sponsor: worker,
+        }) {
+            QueueEvent::Funded(event) => {
+                assert_eq!(event.amount, 700);
+                assert_eq!(event.sponsor, worker);
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&FundingReclaimed {
+            amount: 300,
+            pay_to: worker,
+            queue,
+            sponsor: worker,
+        }) {
+            QueueEvent::FundingReclaimed(event) => {
+                assert_eq!(event.amount, 300);
+                assert_eq!(event.queue, queue);
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+
+        match round_trip(&QueueTokensWithdrawn {
+            amount: 900,
+            mint: worker,
+            pay_to: worker,
+            queue,
+        }) {
+            QueueEvent::TokensWithdrawn(event) => {
+                assert_eq!(event.amount, 900);
+                assert_eq!(event.mint, worker);
+            }
+            event => panic!("Unexpected event {:?}", event),
+        }
+    }
+
+    #[test]
+    fn test_parse_logs_ignores_other_programs() {
+        let other_program = Pubkey::new_unique();
+        let event = QueuePaused {
+            queue: Pubkey::new_unique(),
+        };
+        let logs = vec![
+            invoke(ID, 1),
+            invoke(other_program, 2),
+            program_data(&event),
+            success(other_program),
+            program_data(&event),
+            success(ID),
+            program_data(&event),
+        ];
+        assert_eq!(parse_logs(&logs).len(), 1);
+    }
+}
@@ -0,0 +1,20 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    InstructionData,
+};
+
+pub fn config_migrate(admin: Pubkey, config: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new(admin, true),
+            AccountMeta::new(config, false),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: clockwork_queue_program::instruction::ConfigMigrate {}.data(),
+    }
+}
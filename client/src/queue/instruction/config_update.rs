@@ -6,16 +6,27 @@ use {
         },
         InstructionData,
     },
+    anchor_spl::associated_token::get_associated_token_address,
     clockwork_queue_program::objects::ConfigSettings,
 };
 
 pub fn config_update(admin: Pubkey, config: Pubkey, settings: ConfigSettings) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(admin, true),
+        AccountMeta::new(config, false),
+    ];
+
+    // If a mint is whitelisted, pass the config's token account to withhold token crank fees in.
+    if let Some(fee_mint) = settings.fee_mint {
+        accounts.push(AccountMeta::new_readonly(
+            get_associated_token_address(&config, &fee_mint.mint),
+            false,
+        ));
+    }
+
     Instruction {
         program_id: clockwork_queue_program::ID,
-        accounts: vec![
-            AccountMeta::new(admin, true),
-            AccountMeta::new(config, false),
-        ],
+        accounts,
         data: clockwork_queue_program::instruction::ConfigUpdate { settings }.data(),
     }
 }
@@ -0,0 +1,22 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    clockwork_queue_program::objects::Fee,
+};
+
+pub fn fee_claim(worker: Pubkey, amount: u64, pay_to: Pubkey) -> Instruction {
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new(Fee::pubkey(worker), false),
+            AccountMeta::new(pay_to, false),
+            AccountMeta::new_readonly(worker, true),
+        ],
+        data: clockwork_queue_program::instruction::FeeClaim { amount }.data(),
+    }
+}
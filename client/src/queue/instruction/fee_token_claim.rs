@@ -0,0 +1,27 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    anchor_spl::{associated_token::get_associated_token_address, token},
+    clockwork_queue_program::objects::Fee,
+};
+
+pub fn fee_token_claim(worker: Pubkey, amount: u64, mint: Pubkey, pay_to: Pubkey) -> Instruction {
+    let fee_pubkey = Fee::pubkey(worker);
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(fee_pubkey, false),
+            AccountMeta::new(get_associated_token_address(&fee_pubkey, &mint), false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(pay_to, false),
+            AccountMeta::new_readonly(token::ID, false),
+            AccountMeta::new_readonly(worker, true),
+        ],
+        data: clockwork_queue_program::instruction::FeeTokenClaim { amount }.data(),
+    }
+}
@@ -0,0 +1,32 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    anchor_spl::{associated_token::get_associated_token_address, token},
+    clockwork_queue_program::objects::Config,
+};
+
+pub fn fee_token_withholding_claim(
+    admin: Pubkey,
+    amount: u64,
+    mint: Pubkey,
+    pay_to: Pubkey,
+) -> Instruction {
+    let config_pubkey = Config::pubkey();
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new_readonly(config_pubkey, false),
+            AccountMeta::new(get_associated_token_address(&config_pubkey, &mint), false),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(pay_to, false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data: clockwork_queue_program::instruction::FeeTokenWithholdingClaim { amount }.data(),
+    }
+}
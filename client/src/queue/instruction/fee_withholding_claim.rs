@@ -0,0 +1,28 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    clockwork_queue_program::objects::{Config, Fee},
+};
+
+pub fn fee_withholding_claim(
+    admin: Pubkey,
+    amount: u64,
+    pay_to: Pubkey,
+    worker: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(admin, true),
+            AccountMeta::new_readonly(Config::pubkey(), false),
+            AccountMeta::new(Fee::pubkey(worker), false),
+            AccountMeta::new(pay_to, false),
+        ],
+        data: clockwork_queue_program::instruction::FeeWithholdingClaim { amount }.data(),
+    }
+}
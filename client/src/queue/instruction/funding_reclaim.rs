@@ -0,0 +1,27 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    clockwork_queue_program::objects::Funding,
+};
+
+pub fn funding_reclaim(
+    pay_to: Pubkey,
+    queue: Pubkey,
+    queue_created_at: u64,
+    sponsor: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new(Funding::pubkey(queue, queue_created_at), false),
+            AccountMeta::new(pay_to, false),
+            AccountMeta::new_readonly(sponsor, true),
+        ],
+        data: clockwork_queue_program::instruction::FundingReclaim {}.data(),
+    }
+}
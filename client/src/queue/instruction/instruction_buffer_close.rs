@@ -0,0 +1,23 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    InstructionData,
+};
+
+pub fn instruction_buffer_close(
+    authority: Pubkey,
+    close_to: Pubkey,
+    instruction_buffer: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(close_to, false),
+            AccountMeta::new(instruction_buffer, false),
+        ],
+        data: clockwork_queue_program::instruction::InstructionBufferClose {}.data(),
+    }
+}
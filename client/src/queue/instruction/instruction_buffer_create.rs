@@ -0,0 +1,29 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program,
+        },
+        InstructionData,
+    },
+    clockwork_queue_program::objects::InstructionBuffer,
+};
+
+pub fn instruction_buffer_create(
+    authority: Pubkey,
+    id: String,
+    payer: Pubkey,
+    size: u64,
+) -> Instruction {
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(InstructionBuffer::pubkey(authority, id.clone()), false),
+            AccountMeta::new(payer, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: clockwork_queue_program::instruction::InstructionBufferCreate { id, size }.data(),
+    }
+}
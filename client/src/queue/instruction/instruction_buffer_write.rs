@@ -0,0 +1,43 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+    },
+    InstructionData,
+};
+
+pub fn instruction_buffer_write(
+    authority: Pubkey,
+    data: Vec<u8>,
+    instruction_buffer: Pubkey,
+    offset: u64,
+) -> Instruction {
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new(instruction_buffer, false),
+        ],
+        data: clockwork_queue_program::instruction::InstructionBufferWrite { data, offset }.data(),
+    }
+}
+
+/// Splits the data into chunks of the given size, building an instruction to write each chunk into the buffer.
+pub fn instruction_buffer_write_chunks(
+    authority: Pubkey,
+    chunk_size: usize,
+    data: &[u8],
+    instruction_buffer: Pubkey,
+) -> Vec<Instruction> {
+    data.chunks(chunk_size)
+        .enumerate()
+        .map(|(i, chunk)| {
+            instruction_buffer_write(
+                authority,
+                chunk.to_vec(),
+                instruction_buffer,
+                i.saturating_mul(chunk_size) as u64,
+            )
+        })
+        .collect()
+}
@@ -1,11 +1,37 @@
+mod config_migrate;
 mod config_update;
+mod fee_claim;
+mod fee_token_claim;
+mod fee_token_withholding_claim;
+mod fee_withholding_claim;
+mod funding_reclaim;
 mod initialize;
+mod instruction_buffer_close;
+mod instruction_buffer_create;
+mod instruction_buffer_write;
 mod queue_crank;
 mod queue_create;
+mod queue_fail;
+mod queue_fund;
+mod queue_migrate;
+mod queue_token_withdraw;
 mod queue_update;
 
+pub use config_migrate::*;
 pub use config_update::*;
+pub use fee_claim::*;
+pub use fee_token_claim::*;
+pub use fee_token_withholding_claim::*;
+pub use fee_withholding_claim::*;
+pub use funding_reclaim::*;
 pub use initialize::*;
+pub use instruction_buffer_close::*;
+pub use instruction_buffer_create::*;
+pub use instruction_buffer_write::*;
 pub use queue_crank::*;
 pub use queue_create::*;
+pub use queue_fail::*;
+pub use queue_fund::*;
+pub use queue_migrate::*;
+pub use queue_token_withdraw::*;
 pub use queue_update::*;
@@ -7,30 +7,31 @@ use {
         },
         InstructionData,
     },
-    clockwork_queue_program::objects::{InstructionData as ClockworkInstructionData, Trigger},
+    clockwork_queue_program::objects::{Config, QueueCreateSettings},
 };
 
 pub fn queue_create(
     authority: Pubkey,
-    id: String,
-    kickoff_instruction: Instruction,
     payer: Pubkey,
     queue: Pubkey,
-    trigger: Trigger,
+    settings: QueueCreateSettings,
 ) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new_readonly(Config::pubkey(), false),
+        AccountMeta::new(payer, true),
+        AccountMeta::new(queue, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    // If the queue names its own worker pool, pass the pool account for the program to verify.
+    if let Some(pool) = settings.pool {
+        accounts.push(AccountMeta::new_readonly(pool, false));
+    }
+
     Instruction {
         program_id: clockwork_queue_program::ID,
-        accounts: vec![
-            AccountMeta::new_readonly(authority, true),
-            AccountMeta::new(payer, true),
-            AccountMeta::new(queue, false),
-            AccountMeta::new_readonly(system_program::ID, false),
-        ],
-        data: clockwork_queue_program::instruction::QueueCreate {
-            id,
-            kickoff_instruction: ClockworkInstructionData::from(kickoff_instruction),
-            trigger,
-        }
-        .data(),
+        accounts,
+        data: clockwork_queue_program::instruction::QueueCreate { settings }.data(),
     }
 }
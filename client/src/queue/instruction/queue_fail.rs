@@ -0,0 +1,33 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    clockwork_queue_program::objects::Config,
+};
+
+pub fn queue_fail(
+    attempted_at: u64,
+    data_hash: Option<u64>,
+    pool: Pubkey,
+    queue: Pubkey,
+    worker: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(Config::pubkey(), false),
+            AccountMeta::new_readonly(pool, false),
+            AccountMeta::new(queue, false),
+            AccountMeta::new(worker, true),
+        ],
+        data: clockwork_queue_program::instruction::QueueFail {
+            attempted_at,
+            data_hash,
+        }
+        .data(),
+    }
+}
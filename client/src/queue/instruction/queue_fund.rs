@@ -0,0 +1,29 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+            system_program,
+        },
+        InstructionData,
+    },
+    clockwork_queue_program::objects::Funding,
+};
+
+pub fn queue_fund(
+    amount: u64,
+    queue: Pubkey,
+    queue_created_at: u64,
+    sponsor: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new(Funding::pubkey(queue, queue_created_at), false),
+            AccountMeta::new(queue, false),
+            AccountMeta::new(sponsor, true),
+            AccountMeta::new_readonly(system_program::ID, false),
+        ],
+        data: clockwork_queue_program::instruction::QueueFund { amount }.data(),
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::{
+    solana_program::{
+        instruction::{AccountMeta, Instruction},
+        pubkey::Pubkey,
+        system_program,
+    },
+    InstructionData,
+};
+
+pub fn queue_migrate(
+    authority: Pubkey,
+    queue: Pubkey,
+    watched_account: Option<Pubkey>,
+) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new(authority, true),
+        AccountMeta::new(queue, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    // The account watched by an account trigger is rehashed from its current data
+    if let Some(watched_account) = watched_account {
+        accounts.push(AccountMeta::new_readonly(watched_account, false));
+    }
+
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts,
+        data: clockwork_queue_program::instruction::QueueMigrate {}.data(),
+    }
+}
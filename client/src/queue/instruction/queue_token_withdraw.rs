@@ -0,0 +1,31 @@
+use {
+    anchor_lang::{
+        solana_program::{
+            instruction::{AccountMeta, Instruction},
+            pubkey::Pubkey,
+        },
+        InstructionData,
+    },
+    anchor_spl::{associated_token::get_associated_token_address, token},
+};
+
+pub fn queue_token_withdraw(
+    amount: u64,
+    authority: Pubkey,
+    mint: Pubkey,
+    pay_to: Pubkey,
+    queue: Pubkey,
+) -> Instruction {
+    Instruction {
+        program_id: clockwork_queue_program::ID,
+        accounts: vec![
+            AccountMeta::new_readonly(authority, true),
+            AccountMeta::new_readonly(mint, false),
+            AccountMeta::new(pay_to, false),
+            AccountMeta::new_readonly(queue, false),
+            AccountMeta::new(get_associated_token_address(&queue, &mint), false),
+            AccountMeta::new_readonly(token::ID, false),
+        ],
+        data: clockwork_queue_program::instruction::QueueTokenWithdraw { amount }.data(),
+    }
+}
@@ -7,28 +7,25 @@ use {
         },
         InstructionData,
     },
-    clockwork_queue_program::objects::{InstructionData as ClockworkInstructionData, Trigger},
+    clockwork_queue_program::objects::{Config, QueueSettings},
 };
 
-pub fn queue_update(
-    authority: Pubkey,
-    queue: Pubkey,
-    kickoff_instruction: Option<ClockworkInstructionData>,
-    rate_limit: Option<u64>,
-    trigger: Option<Trigger>,
-) -> Instruction {
+pub fn queue_update(authority: Pubkey, queue: Pubkey, settings: QueueSettings) -> Instruction {
+    let mut accounts = vec![
+        AccountMeta::new_readonly(authority, true),
+        AccountMeta::new_readonly(Config::pubkey(), false),
+        AccountMeta::new(queue, false),
+        AccountMeta::new_readonly(system_program::ID, false),
+    ];
+
+    // If the queue's worker pool is being set, pass the pool account for the program to verify.
+    if let Some(Some(pool)) = settings.pool {
+        accounts.push(AccountMeta::new_readonly(pool, false));
+    }
+
     Instruction {
         program_id: clockwork_queue_program::ID,
-        accounts: vec![
-            AccountMeta::new_readonly(authority, true),
-            AccountMeta::new(queue, false),
-            AccountMeta::new_readonly(system_program::ID, false),
-        ],
-        data: clockwork_queue_program::instruction::QueueUpdate {
-            kickoff_instruction,
-            rate_limit,
-            trigger,
-        }
-        .data(),
+        accounts,
+        data: clockwork_queue_program::instruction::QueueUpdate { settings }.data(),
     }
 }
@@ -1,3 +1,4 @@
+pub mod events;
 pub mod instruction;
 
 pub use clockwork_queue_program::errors;
@@ -1,5 +1,5 @@
 use chrono::offset::TimeZone;
-use chrono::{DateTime, Datelike, Timelike};
+use chrono::{DateTime, Datelike, NaiveDate, Timelike};
 use std::fmt::{Display, Formatter, Result as FmtResult};
 use std::ops::Bound::{Included, Unbounded};
 
@@ -24,6 +24,7 @@ impl Schedule {
         Schedule { source, fields }
     }
 
+    /// The first moment in the schedule strictly after the given datetime, evaluated in its timezone.
     pub fn next_after<Z>(&self, after: &DateTime<Z>) -> Option<DateTime<Z>>
     where
         Z: TimeZone,
@@ -86,14 +87,17 @@ impl Schedule {
                             for second in
                                 self.fields.seconds.ordinals().range(second_range).cloned()
                             {
-                                let timezone = after.timezone();
-                                let candidate = if let Some(candidate) = timezone
-                                    .ymd(year as i32, month, day_of_month)
-                                    .and_hms_opt(hour, minute, second)
-                                {
-                                    candidate
-                                } else {
-                                    continue;
+                                let candidate = match local_datetime(
+                                    &after.timezone(),
+                                    year,
+                                    month,
+                                    day_of_month,
+                                    hour,
+                                    minute,
+                                    second,
+                                ) {
+                                    Some(candidate) if candidate.gt(after) => candidate,
+                                    _ => continue,
                                 };
                                 if !self
                                     .fields
@@ -119,6 +123,7 @@ impl Schedule {
         None
     }
 
+    /// The last moment in the schedule strictly before the given datetime, evaluated in its timezone.
     pub fn prev_before<Z>(&self, before: &DateTime<Z>) -> Option<DateTime<Z>>
     where
         Z: TimeZone,
@@ -216,14 +221,17 @@ impl Schedule {
                                 .rev()
                                 .cloned()
                             {
-                                let timezone = before.timezone();
-                                let candidate = if let Some(candidate) = timezone
-                                    .ymd(year as i32, month, day_of_month)
-                                    .and_hms_opt(hour, minute, second)
-                                {
-                                    candidate
-                                } else {
-                                    continue;
+                                let candidate = match local_datetime(
+                                    &before.timezone(),
+                                    year,
+                                    month,
+                                    day_of_month,
+                                    hour,
+                                    minute,
+                                    second,
+                                ) {
+                                    Some(candidate) if candidate.lt(before) => candidate,
+                                    _ => continue,
                                 };
                                 if !self
                                     .fields
@@ -332,6 +340,27 @@ impl Schedule {
     }
 }
 
+/// Resolves a local date and time in a timezone.
+///
+/// Local times which do not exist in the timezone (e.g. skipped by a DST transition) resolve to none.
+/// Local times which occur twice (e.g. repeated by a DST transition) resolve to their first occurrence.
+fn local_datetime<Z>(
+    timezone: &Z,
+    year: Ordinal,
+    month: Ordinal,
+    day_of_month: Ordinal,
+    hour: Ordinal,
+    minute: Ordinal,
+    second: Ordinal,
+) -> Option<DateTime<Z>>
+where
+    Z: TimeZone,
+{
+    NaiveDate::from_ymd_opt(year as i32, month, day_of_month)
+        .and_then(|date| date.and_hms_opt(hour, minute, second))
+        .and_then(|naive_datetime| timezone.from_local_datetime(&naive_datetime).earliest())
+}
+
 impl Display for Schedule {
     fn fmt(&self, f: &mut Formatter<'_>) -> FmtResult {
         write!(f, "{}", self.source)
@@ -370,4 +370,66 @@ mod tests {
         assert!(schedule.includes(included));
         assert!(!schedule.includes(not_included));
     }
+
+    #[test]
+    fn test_next_after_fixed_local_time_across_dst() {
+        // 9:00 in New York is 14:00 UTC in winter and 13:00 UTC in summer.
+        let schedule = Schedule::from_str("0 0 9 * * *").unwrap();
+        let schedule_tz: Tz = "America/New_York".parse().unwrap();
+        let after = schedule_tz.ymd(2022, 3, 12).and_hms(12, 0, 0);
+        let first = schedule.next_after(&after).unwrap();
+        let second = schedule.next_after(&first).unwrap();
+        assert_eq!(
+            first.with_timezone(&Utc),
+            Utc.ymd(2022, 3, 13).and_hms(13, 0, 0)
+        );
+        assert_eq!(
+            second.with_timezone(&Utc),
+            Utc.ymd(2022, 3, 14).and_hms(13, 0, 0)
+        );
+    }
+
+    #[test]
+    fn test_next_after_skips_dst_gap() {
+        // 02:30 does not exist in New York on 2022-03-13.
+        let schedule = Schedule::from_str("0 30 2 * * *").unwrap();
+        let schedule_tz: Tz = "America/New_York".parse().unwrap();
+        let after = schedule_tz.ymd(2022, 3, 12).and_hms(12, 0, 0);
+        let next = schedule.next_after(&after).unwrap();
+        assert_eq!(next, schedule_tz.ymd(2022, 3, 14).and_hms(2, 30, 0));
+    }
+
+    #[test]
+    fn test_next_after_fires_once_in_dst_overlap() {
+        // 01:30 occurs twice in New York on 2022-11-06. Only the first occurrence is scheduled.
+        let schedule = Schedule::from_str("0 30 1 * * *").unwrap();
+        let schedule_tz: Tz = "America/New_York".parse().unwrap();
+        let after = schedule_tz.ymd(2022, 11, 6).and_hms(0, 0, 0);
+        let first = schedule.next_after(&after).unwrap();
+        let second = schedule.next_after(&first).unwrap();
+        assert_eq!(
+            first.with_timezone(&Utc),
+            Utc.ymd(2022, 11, 6).and_hms(5, 30, 0)
+        );
+        assert_eq!(
+            second.with_timezone(&Utc),
+            Utc.ymd(2022, 11, 7).and_hms(6, 30, 0)
+        );
+    }
+
+    #[test]
+    fn test_prev_before_in_dst_overlap() {
+        // From the second occurrence of 01:45, the previous 01:30 is its first occurrence.
+        let schedule = Schedule::from_str("0 30 1 * * *").unwrap();
+        let schedule_tz: Tz = "America/New_York".parse().unwrap();
+        let before = Utc
+            .ymd(2022, 11, 6)
+            .and_hms(6, 45, 0)
+            .with_timezone(&schedule_tz);
+        let prev = schedule.prev_before(&before).unwrap();
+        assert_eq!(
+            prev.with_timezone(&Utc),
+            Utc.ymd(2022, 11, 6).and_hms(5, 30, 0)
+        );
+    }
 }
@@ -13,11 +13,13 @@ use {
     solana_program::{clock::Clock, pubkey::Pubkey, sysvar},
 };
 
+#[allow(clippy::large_enum_variant)]
 pub enum AccountUpdateEvent {
     Clock { clock: Clock },
     HttpRequest { request: Request },
     Pool { pool: Pool },
     Queue { queue: Queue },
+    QueueClosed,
     Rotator { rotator: Rotator },
     Snapshot { snapshot: Snapshot },
 }
@@ -39,6 +41,11 @@ impl TryFrom<ReplicaAccountInfo<'_>> for AccountUpdateEvent {
             });
         }
 
+        // If the account is a closed queue program account, return it
+        if owner_pubkey.eq(&clockwork_client::queue::ID) && account_info.lamports == 0 {
+            return Ok(AccountUpdateEvent::QueueClosed);
+        }
+
         if owner_pubkey.eq(&clockwork_client::queue::ID) && account_info.data.len() > 8 {
             let d = &account_info.data[..8];
             if d.eq(&Queue::discriminator()) {
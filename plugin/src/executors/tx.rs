@@ -7,8 +7,8 @@ use {
     solana_geyser_plugin_interface::geyser_plugin_interface::{
         GeyserPluginError, Result as PluginResult,
     },
-    solana_program::{hash::Hash, message::Message},
-    solana_sdk::{commitment_config::CommitmentConfig, transaction::Transaction},
+    solana_program::{hash::Hash, message::VersionedMessage},
+    solana_sdk::{commitment_config::CommitmentConfig, transaction::VersionedTransaction},
     std::{fmt::Debug, sync::Arc},
     tokio::runtime::Runtime,
 };
@@ -67,7 +67,7 @@ impl TxExecutor {
             .clone()
             .build_rotation_tx(self.clockwork_client.clone(), slot)
             .await
-            .and_then(|tx| match self.execute_tx(slot, &tx) {
+            .and_then(|tx| match self.execute_tx(slot, &tx.into()) {
                 Ok(()) => Ok(()),
                 Err(err) => {
                     info!("Failed to rotate pools: {}", err);
@@ -77,20 +77,15 @@ impl TxExecutor {
     }
 
     async fn crank_queues(self: Arc<Self>, slot: u64) -> PluginResult<()> {
-        // Exit early if we are not in the worker pool.
+        // Get this node's positions in the worker pools.
         let r_pool_positions = self.observers.pool.pool_positions.read().await;
-        let crank_pool = r_pool_positions.crank_pool.clone();
+        let pool_positions = r_pool_positions.clone();
         drop(r_pool_positions);
-        if crank_pool.current_position.is_none() && !crank_pool.workers.is_empty() {
-            return Err(GeyserPluginError::Custom(
-                "This node is not an authorized worker".into(),
-            ));
-        }
 
         self.observers
             .queue
             .clone()
-            .build_crank_txs(self.clockwork_client.clone(), slot)
+            .build_crank_txs(self.clockwork_client.clone(), pool_positions, slot)
             .await
             .iter()
             .for_each(|tx| {
@@ -106,11 +101,11 @@ impl TxExecutor {
         Ok(())
     }
 
-    fn execute_tx(self: Arc<Self>, slot: u64, tx: &Transaction) -> PluginResult<()> {
+    fn execute_tx(self: Arc<Self>, slot: u64, tx: &VersionedTransaction) -> PluginResult<()> {
         // Exit early if this message was sent recently
         if let Some(entry) = self
             .message_history
-            .get(&tx.message().blockhash_agnostic_hash())
+            .get(&tx.message.blockhash_agnostic_hash())
         {
             let msg_slot = entry.value();
             if slot < msg_slot + MESSAGE_DEDUPE_PERIOD {
@@ -125,13 +120,15 @@ impl TxExecutor {
             .and_then(|tx| self.log_tx(slot, tx))
     }
 
-    fn simulate_tx(self: Arc<Self>, tx: &Transaction) -> PluginResult<Transaction> {
+    fn simulate_tx(
+        self: Arc<Self>,
+        tx: &VersionedTransaction,
+    ) -> PluginResult<VersionedTransaction> {
         // TODO Only submit this transaction if the simulated increase in this worker's
         //      Fee account balance is greater than the lamports spent by the worker.
 
-        self.tpu_client
-            .rpc_client()
-            .simulate_transaction_with_config(
+        self.clockwork_client
+            .simulate_versioned_transaction_with_config(
                 tx,
                 RpcSimulateTransactionConfig {
                     replace_recent_blockhash: true,
@@ -154,8 +151,10 @@ impl TxExecutor {
             })?
     }
 
-    fn submit_tx(self: Arc<Self>, tx: &Transaction) -> PluginResult<Transaction> {
-        if !self.tpu_client.send_transaction(tx) {
+    fn submit_tx(self: Arc<Self>, tx: &VersionedTransaction) -> PluginResult<VersionedTransaction> {
+        let wire_transaction = bincode::serialize(tx)
+            .map_err(|_err| GeyserPluginError::Custom("Failed to serialize transaction".into()))?;
+        if !self.tpu_client.send_wire_transaction(wire_transaction) {
             return Err(GeyserPluginError::Custom(
                 "Failed to send transaction".into(),
             ));
@@ -163,9 +162,9 @@ impl TxExecutor {
         Ok(tx.clone())
     }
 
-    fn log_tx(self: Arc<Self>, slot: u64, tx: Transaction) -> PluginResult<()> {
+    fn log_tx(self: Arc<Self>, slot: u64, tx: VersionedTransaction) -> PluginResult<()> {
         self.message_history
-            .insert(tx.message().blockhash_agnostic_hash(), slot);
+            .insert(tx.message.blockhash_agnostic_hash(), slot);
         let sig = tx.signatures[0];
         info!("slot: {} sig: {}", slot, sig);
         Ok(())
@@ -193,14 +192,10 @@ trait BlockhashAgnosticHash {
     fn blockhash_agnostic_hash(&self) -> Hash;
 }
 
-impl BlockhashAgnosticHash for Message {
+impl BlockhashAgnosticHash for VersionedMessage {
     fn blockhash_agnostic_hash(&self) -> Hash {
-        Message {
-            header: self.header.clone(),
-            account_keys: self.account_keys.clone(),
-            recent_blockhash: Hash::default(),
-            instructions: self.instructions.clone(),
-        }
-        .hash()
+        let mut message = self.clone();
+        message.set_recent_blockhash(Hash::default());
+        message.hash()
     }
 }
@@ -12,7 +12,7 @@ use {
     },
     solana_program::pubkey::Pubkey,
     solana_sdk::{signer::Signer, transaction::Transaction},
-    std::{cmp::Ordering, fmt::Debug, sync::Arc},
+    std::{cmp::Ordering, collections::HashMap, fmt::Debug, sync::Arc},
     tokio::{runtime::Runtime, sync::RwLock},
 };
 
@@ -22,8 +22,8 @@ pub struct PoolObserver {
     // Plugin config values.
     pub config: PluginConfig,
 
-    // RwLock for this node's position in the worker pools.
-    pub pool_positions: Arc<RwLock<PoolPositions>>,
+    // RwLock for this node's position in each of the worker pools, keyed by pool pubkey.
+    pub pool_positions: Arc<RwLock<HashMap<Pubkey, PoolPosition>>>,
 
     // Pub worker address
     pub pubkey: Pubkey,
@@ -45,7 +45,7 @@ impl PoolObserver {
     pub fn new(config: PluginConfig, runtime: Arc<Runtime>) -> Self {
         Self {
             config: config.clone(),
-            pool_positions: Arc::new(RwLock::new(PoolPositions::default())),
+            pool_positions: Arc::new(RwLock::new(HashMap::new())),
             pubkey: read_or_new_keypair(config.keypath).pubkey(),
             rotator: RwLock::new(Rotator {
                 last_rotation_at: 0,
@@ -72,10 +72,13 @@ impl PoolObserver {
         })
     }
 
-    pub fn handle_updated_pool(self: Arc<Self>, pool: Pool, _slot: u64) -> PluginResult<()> {
+    pub fn handle_updated_pool(
+        self: Arc<Self>,
+        pool: Pool,
+        pool_pubkey: Pubkey,
+        _slot: u64,
+    ) -> PluginResult<()> {
         self.spawn(|this| async move {
-            // TODO Parse for pool name
-
             info!("Updated pool: {:#?}", pool);
 
             // Build the new pool_position
@@ -90,22 +93,8 @@ impl PoolObserver {
                 workers: workers.make_contiguous().to_vec().clone(),
             };
 
-            // Update the pool positions struct
-            match pool.name.as_str() {
-                "crank" => {
-                    *w_pool_positions = PoolPositions {
-                        crank_pool: pool_position,
-                        http_pool: w_pool_positions.http_pool.clone(),
-                    };
-                }
-                "http" => {
-                    *w_pool_positions = PoolPositions {
-                        crank_pool: w_pool_positions.crank_pool.clone(),
-                        http_pool: pool_position,
-                    };
-                }
-                _ => {}
-            }
+            // Update the pool positions map
+            w_pool_positions.insert(pool_pubkey, pool_position);
 
             drop(w_pool_positions);
             Ok(())
@@ -163,11 +152,18 @@ impl PoolObserver {
             ));
         }
 
-        // Exit early if this node is not in the worker pool AND
+        // Exit early if this node is not in any of the rotated worker pools AND
         //  we are still within the pool's grace period.
-        if r_pool_positions.crank_pool.current_position.is_none()
-            && slot < target_slot + GRACE_PERIOD
-        {
+        let is_worker = r_rotator.pool_pubkeys.iter().any(|pool_pubkey| {
+            matches!(
+                r_pool_positions.get(pool_pubkey),
+                Some(PoolPosition {
+                    current_position: Some(_),
+                    ..
+                })
+            )
+        });
+        if !is_worker && slot < target_slot + GRACE_PERIOD {
             return Err(GeyserPluginError::Custom(
                 "This node is not a worker, and it is within the rotation grace period".into(),
             ));
@@ -269,18 +265,3 @@ impl Default for PoolPosition {
         }
     }
 }
-
-#[derive(Clone)]
-pub struct PoolPositions {
-    pub crank_pool: PoolPosition,
-    pub http_pool: PoolPosition,
-}
-
-impl Default for PoolPositions {
-    fn default() -> Self {
-        PoolPositions {
-            crank_pool: PoolPosition::default(),
-            http_pool: PoolPosition::default(),
-        }
-    }
-}
@@ -1,10 +1,16 @@
 use {
-    chrono::{DateTime, NaiveDateTime, Utc},
+    crate::observers::pool::PoolPosition,
     clockwork_client::{
-        queue::objects::{Queue, Trigger, TriggerContext},
-        Client as ClockworkClient,
+        address_lookup_table::{compile_v0_message, AddressLookupTableAccount},
+        queue::{
+            objects::{
+                AccountsTriggerMode, Config, Fee, Funding, InstructionBuffer, InstructionData,
+                Queue, QueueInstruction, Trigger, TriggerContext, MAX_COMPUTE_UNIT_LIMIT,
+            },
+            utils::{account_data_hash, next_cron_timestamp},
+        },
+        Client as ClockworkClient, ClientResult,
     },
-    clockwork_cron::Schedule,
     dashmap::{DashMap, DashSet},
     log::info,
     solana_account_decoder::UiAccountEncoding,
@@ -16,48 +22,82 @@ use {
     },
     solana_program::{
         clock::Clock,
+        hash::Hash,
         instruction::{AccountMeta, Instruction},
+        message::{Message, VersionedMessage},
         pubkey::Pubkey,
+        sysvar,
     },
-    solana_sdk::{account::Account, commitment_config::CommitmentConfig, transaction::Transaction},
-    std::{
-        collections::hash_map::DefaultHasher,
-        fmt::Debug,
-        hash::{Hash, Hasher},
-        str::FromStr,
-        sync::Arc,
+    solana_sdk::{
+        account::Account,
+        commitment_config::CommitmentConfig,
+        compute_budget::ComputeBudgetInstruction,
+        signature::{Keypair, Signer},
+        transaction::{Transaction, VersionedTransaction},
     },
+    spl_associated_token_account::get_associated_token_address,
+    std::{collections::HashMap, fmt::Debug, str::FromStr, sync::Arc},
     tokio::runtime::Runtime,
 };
 
 static COMPUTE_BUDGET_LIMIT: u64 = 1_400_000; // Max number of compute units per transaction
+static CONDITION_POLL_BACKOFF_LIMIT: u64 = 32; // Max number of slots to wait between polls of a condition queue's predicate
+static CRANK_BUILD_TIME_LIMIT: u128 = 400; // Max number of milliseconds to spend building crank transactions per slot
 static TRANSACTION_SIZE_LIMIT: usize = 1_232; // Max byte size of a serialized transaction
 
 pub struct QueueObserver {
     // Map from slot numbers to the sysvar clock data for that slot.
     pub clocks: DashMap<u64, Clock>,
 
-    // The set of the queues that are currently crankable (i.e. have a next_instruction)
+    // Map from the pubkeys of queues with a condition trigger to the schedule their predicates are polled on.
+    pub condition_queues: DashMap<Pubkey, ConditionPoll>,
+
+    // Map from queue pubkeys to the number of thread executions each queue has completed.
+    pub completion_counts: DashMap<Pubkey, u64>,
+
+    // The set of the queues that are currently crankable (i.e. have next_instructions)
     pub crankable_queues: DashSet<Pubkey>,
 
     // Map from unix timestamps to the list of queues scheduled for that moment.
     pub cron_queues: DashMap<i64, DashSet<Pubkey>>,
 
-    // Map from account pubkeys to the set of queues listening for an account update.
-    pub listener_queues: DashMap<Pubkey, DashSet<Pubkey>>,
+    // Map from queue pubkeys to the queues following them, with the completion count at each follower's last execution.
+    pub dependent_queues: DashMap<Pubkey, DashMap<Pubkey, u64>>,
+
+    // Map from epoch numbers to the list of queues scheduled for that epoch.
+    pub epoch_queues: DashMap<u64, DashSet<Pubkey>>,
+
+    // Map from queue pubkeys to the fee bids of the queues which have one.
+    pub fee_bids: DashMap<Pubkey, u64>,
+
+    // Map from queue pubkeys to the expiry settings of queues with an expiration time.
+    pub expiring_queues: DashMap<Pubkey, QueueExpiry>,
+
+    // Map from account pubkeys to the queues listening for an account update, keyed by queue pubkey.
+    pub listener_queues: DashMap<Pubkey, DashMap<Pubkey, AccountListener>>,
 
     // Tokio runtime for processing async tasks.
     pub runtime: Arc<Runtime>,
+
+    // Map from slot numbers to the list of queues scheduled for that slot.
+    pub slot_queues: DashMap<u64, DashSet<Pubkey>>,
 }
 
 impl QueueObserver {
     pub fn new(runtime: Arc<Runtime>) -> Self {
         Self {
             clocks: DashMap::new(),
+            completion_counts: DashMap::new(),
+            condition_queues: DashMap::new(),
             crankable_queues: DashSet::new(),
             cron_queues: DashMap::new(),
+            dependent_queues: DashMap::new(),
+            epoch_queues: DashMap::new(),
+            expiring_queues: DashMap::new(),
+            fee_bids: DashMap::new(),
             listener_queues: DashMap::new(),
             runtime,
+            slot_queues: DashMap::new(),
         }
     }
 
@@ -82,24 +122,62 @@ impl QueueObserver {
     pub fn handle_updated_account(
         self: Arc<Self>,
         account_pubkey: Pubkey,
-        _account_replica: ReplicaAccountInfo,
+        account_replica: ReplicaAccountInfo,
     ) -> PluginResult<()> {
+        // Exit early if no queues are listening to this account.
+        if !self.listener_queues.contains_key(&account_pubkey) {
+            return Ok(());
+        }
+
+        let data = account_replica.data.to_vec();
         self.spawn(|this| async move {
-            // Move all queues listening to this account into the crankable set.
-            this.listener_queues.retain(|pubkey, queue_pubkeys| {
+            // Move all queues whose watched bytes have changed into the crankable set.
+            // Queues waiting for all of their accounts to change are only moved once none of their listeners remain.
+            let mut all_of_queues = vec![];
+            this.listener_queues.retain(|pubkey, listeners| {
                 if account_pubkey.eq(pubkey) {
-                    for queue_pubkey in queue_pubkeys.iter() {
-                        this.crankable_queues.insert(*queue_pubkey.key());
-                    }
-                    false
+                    listeners.retain(|queue_pubkey, listener| {
+                        let data_hash = account_data_hash(&data, listener.offset, listener.size);
+                        if listener.data_hash.ne(&Some(data_hash)) {
+                            match listener.mode {
+                                AccountsTriggerMode::All => all_of_queues.push(*queue_pubkey),
+                                AccountsTriggerMode::Any => {
+                                    this.crankable_queues.insert(*queue_pubkey);
+                                }
+                            }
+                            false
+                        } else {
+                            true
+                        }
+                    });
+                    !listeners.is_empty()
                 } else {
                     true
                 }
             });
+            for queue_pubkey in all_of_queues {
+                let is_listening = this
+                    .listener_queues
+                    .iter()
+                    .any(|listeners| listeners.contains_key(&queue_pubkey));
+                if !is_listening {
+                    this.crankable_queues.insert(queue_pubkey);
+                }
+            }
 
-            // TODO This account update could have just been a lamport change (not a data update).
-            // TODO To optimize, we need to fetch the queue accounts to verify the data update
+            Ok(())
+        })
+    }
 
+    pub fn handle_closed_queue(self: Arc<Self>, queue_pubkey: Pubkey) -> PluginResult<()> {
+        self.spawn(|this| async move {
+            info!("Dropping closed queue {:#?}", queue_pubkey);
+            this.completion_counts.remove(&queue_pubkey);
+            this.crankable_queues.remove(&queue_pubkey);
+            this.dependent_queues.remove(&queue_pubkey);
+            this.expiring_queues.remove(&queue_pubkey);
+            this.fee_bids.remove(&queue_pubkey);
+            this.unindex_queue(&queue_pubkey);
             Ok(())
         })
     }
@@ -112,54 +190,146 @@ impl QueueObserver {
         self.spawn(|this| async move {
             info!("Caching queue {:#?} {:#?}", queue_pubkey, queue);
 
-            // Remove queue from crankable set
+            // Track the queue's completion count, for indexing the queues which follow it.
+            this.completion_counts
+                .insert(queue_pubkey, queue.completion_count);
+
+            // Move all queues following this one into the crankable set if it has completed a new thread execution.
+            if let Some(dependents) = this.dependent_queues.get(&queue_pubkey) {
+                dependents.retain(|dependent_pubkey, completion_count| {
+                    if queue.completion_count > *completion_count {
+                        this.crankable_queues.insert(*dependent_pubkey);
+                        false
+                    } else {
+                        true
+                    }
+                });
+            }
+            this.dependent_queues
+                .remove_if(&queue_pubkey, |_queue_pubkey, dependents| dependents.is_empty());
+
+            // Track the queue's fee bid.
+            match queue.fee_bid {
+                None => {
+                    this.fee_bids.remove(&queue_pubkey);
+                }
+                Some(fee_bid) => {
+                    this.fee_bids.insert(queue_pubkey, fee_bid);
+                }
+            }
+
+            // Remove queue from crankable, condition, and expiring sets
+            this.condition_queues.remove(&queue_pubkey);
             this.crankable_queues.remove(&queue_pubkey);
+            this.expiring_queues.remove(&queue_pubkey);
 
-            // If the queue is paused, just return without indexing
+            // If the queue is paused, drop it from the trigger indexes so it is not cranked until it is resumed.
             if queue.paused {
+                this.unindex_queue(&queue_pubkey);
+                return Ok(());
+            }
+
+            // If the queue has reached its maximum number of executions, drop it from the trigger indexes.
+            // Queues which will auto-close are cranked one last time to be closed.
+            let has_reached_max_executions = matches!(queue.max_executions, Some(max_executions) if queue.exec_count >= max_executions);
+            if has_reached_max_executions && queue.next_instructions.is_empty() {
+                this.expire_queue(queue_pubkey, queue.auto_close);
                 return Ok(());
             }
 
-            if queue.next_instruction.is_some() {
-                // If the queue has a next instruction, index it as crankable.
+            // Track the queue's expiration time.
+            if let Some(expires_at) = queue.expires_at {
+                this.expiring_queues.insert(
+                    queue_pubkey,
+                    QueueExpiry {
+                        auto_close: queue.auto_close,
+                        expires_at,
+                    },
+                );
+            }
+
+            if !queue.next_instructions.is_empty() || queue.consecutive_failures > 0 {
+                // If the queue has next instructions or is retrying a failed crank, index it as crankable.
                 this.crankable_queues.insert(queue_pubkey);
             } else {
                 // Otherwise, index the queue according to its trigger type.
+                let trigger_context = queue.trigger_context();
                 match queue.trigger {
                     Trigger::Account {
                         pubkey: account_pubkey,
+                        offset,
+                        size,
                     } => {
+                        // Find the data hash of the watched bytes at the queue's last execution.
+                        let data_hash = match trigger_context {
+                            None => None,
+                            Some(TriggerContext::Account { data_hash }) => Some(data_hash),
+                            Some(_) => {
+                                return Err(GeyserPluginError::Custom(
+                                    "Invalid exec context".into(),
+                                ))
+                            }
+                        };
+
                         // Index the queue by its trigger's account pubkey.
-                        this.listener_queues
-                            .entry(account_pubkey)
-                            .and_modify(|v| {
-                                v.insert(queue_pubkey);
-                            })
-                            .or_insert_with(|| {
-                                let v = DashSet::new();
-                                v.insert(queue_pubkey);
-                                v
-                            });
+                        let listener = AccountListener {
+                            data_hash,
+                            mode: AccountsTriggerMode::Any,
+                            offset,
+                            size,
+                        };
+                        this.index_listener(account_pubkey, listener, queue_pubkey);
+                    }
+                    Trigger::Accounts { accounts, mode } => {
+                        // Find the data hashes of the watched bytes at the queue's last execution.
+                        let data_hashes = match trigger_context {
+                            None => None,
+                            Some(TriggerContext::Accounts { data_hashes }) => Some(data_hashes),
+                            Some(_) => {
+                                return Err(GeyserPluginError::Custom(
+                                    "Invalid exec context".into(),
+                                ))
+                            }
+                        };
+
+                        // Index the queue by each of its trigger's account pubkeys.
+                        for (i, watched_account) in accounts.iter().enumerate() {
+                            let listener = AccountListener {
+                                data_hash: data_hashes.map(|data_hashes| data_hashes[i]),
+                                mode,
+                                offset: watched_account.offset,
+                                size: watched_account.size,
+                            };
+                            this.index_listener(watched_account.pubkey, listener, queue_pubkey);
+                        }
+                    }
+                    Trigger::Condition { predicate: _ } => {
+                        // Index the queue to be polled, starting from the next slot.
+                        this.condition_queues
+                            .insert(queue_pubkey, ConditionPoll::default());
                     }
                     Trigger::Cron {
                         schedule,
                         skippable: _,
+                        timezone,
                     } => {
                         // Find a reference timestamp for calculating the queue's upcoming target time.
-                        let reference_timestamp = match queue.exec_context {
+                        let reference_timestamp = match trigger_context {
                             None => queue.created_at.unix_timestamp,
-                            Some(exec_context) => match exec_context.trigger_context {
-                                TriggerContext::Cron { started_at } => started_at,
-                                _ => {
-                                    return Err(GeyserPluginError::Custom(
-                                        "Invalid exec context".into(),
-                                    ))
-                                }
-                            },
+                            Some(TriggerContext::Cron { started_at }) => started_at,
+                            Some(_) => {
+                                return Err(GeyserPluginError::Custom(
+                                    "Invalid exec context".into(),
+                                ))
+                            }
                         };
 
                         // Index the queue to its target timestamp
-                        match next_moment(reference_timestamp, schedule) {
+                        match next_cron_timestamp(
+                            reference_timestamp,
+                            &schedule,
+                            timezone.as_deref(),
+                        ) {
                             None => {} // The queue does not have any upcoming scheduled target time
                             Some(target_timestamp) => {
                                 this.cron_queues
@@ -175,9 +345,70 @@ impl QueueObserver {
                             }
                         }
                     }
+                    Trigger::Epoch { epoch } => {
+                        // Index the queue to its target epoch.
+                        this.epoch_queues
+                            .entry(epoch)
+                            .and_modify(|v| {
+                                v.insert(queue_pubkey);
+                            })
+                            .or_insert_with(|| {
+                                let v = DashSet::new();
+                                v.insert(queue_pubkey);
+                                v
+                            });
+                    }
                     Trigger::Immediate => {
                         this.crankable_queues.insert(queue_pubkey);
                     }
+                    Trigger::Queue {
+                        queue: followed_queue_pubkey,
+                    } => {
+                        // Find the completion count of the followed queue at this queue's last execution.
+                        let completion_count = match trigger_context {
+                            None => 0,
+                            Some(TriggerContext::Queue { completion_count }) => completion_count,
+                            Some(_) => {
+                                return Err(GeyserPluginError::Custom(
+                                    "Invalid exec context".into(),
+                                ))
+                            }
+                        };
+
+                        // If the followed queue has already completed a thread execution since then, index the queue
+                        // as crankable. Otherwise, index it by the pubkey of the queue it follows.
+                        let has_completed = matches!(
+                            this.completion_counts.get(&followed_queue_pubkey),
+                            Some(followed_completion_count) if *followed_completion_count > completion_count
+                        );
+                        if has_completed {
+                            this.crankable_queues.insert(queue_pubkey);
+                        } else {
+                            this.dependent_queues
+                                .entry(followed_queue_pubkey)
+                                .and_modify(|v| {
+                                    v.insert(queue_pubkey, completion_count);
+                                })
+                                .or_insert_with(|| {
+                                    let v = DashMap::new();
+                                    v.insert(queue_pubkey, completion_count);
+                                    v
+                                });
+                        }
+                    }
+                    Trigger::Slot { slot } => {
+                        // Index the queue to its target slot.
+                        this.slot_queues
+                            .entry(slot)
+                            .and_modify(|v| {
+                                v.insert(queue_pubkey);
+                            })
+                            .or_insert_with(|| {
+                                let v = DashSet::new();
+                                v.insert(queue_pubkey);
+                                v
+                            });
+                    }
                 }
             }
 
@@ -198,14 +429,24 @@ impl QueueObserver {
     pub async fn build_crank_txs(
         self: Arc<Self>,
         client: Arc<ClockworkClient>,
+        pool_positions: HashMap<Pubkey, PoolPosition>,
         slot: u64,
-    ) -> Vec<Transaction> {
+    ) -> Vec<VersionedTransaction> {
         // Get the clock for this slot.
         let clock = match self.clocks.get(&slot) {
             None => return vec![],
             Some(clock) => clock.value().clone(),
         };
 
+        // Drop all of the queues that have expired.
+        self.expiring_queues.retain(|queue_pubkey, expiry| {
+            let is_expired = clock.unix_timestamp >= expiry.expires_at;
+            if is_expired {
+                self.expire_queue(*queue_pubkey, expiry.auto_close);
+            }
+            !is_expired
+        });
+
         // Index all of the scheduled queues that are now due.
         // Cache retains all queues that are not yet due.
         self.cron_queues.retain(|target_timestamp, queue_pubkeys| {
@@ -218,55 +459,178 @@ impl QueueObserver {
             !is_due
         });
 
-        // Build the set of crank transactions
-        // TODO Use rayon to parallelize this operation
-        self.crankable_queues
+        // Index all of the slot and epoch queues that are now due.
+        self.slot_queues.retain(|target_slot, queue_pubkeys| {
+            let is_due = clock.slot >= *target_slot;
+            if is_due {
+                for queue_pubkey_ref in queue_pubkeys.iter() {
+                    self.crankable_queues.insert(*queue_pubkey_ref.key());
+                }
+            }
+            !is_due
+        });
+        self.epoch_queues.retain(|target_epoch, queue_pubkeys| {
+            let is_due = clock.epoch >= *target_epoch;
+            if is_due {
+                for queue_pubkey_ref in queue_pubkeys.iter() {
+                    self.crankable_queues.insert(*queue_pubkey_ref.key());
+                }
+            }
+            !is_due
+        });
+
+        // Poll the condition queues which are due. A crank is only packed if its simulation finds the predicate to be true.
+        for condition_poll in self.condition_queues.iter() {
+            if slot >= condition_poll.poll_at {
+                self.crankable_queues.insert(*condition_poll.key());
+            }
+        }
+
+        // Get the default worker pool for queues which do not name their own.
+        let worker_pool = match client.get::<Config>(&Config::pubkey()) {
+            Err(_err) => return vec![],
+            Ok(config) => config.worker_pool,
+        };
+
+        // Get the signature fee rate to report to the program, since the runtime does not expose it to programs.
+        let lamports_per_signature = match client.get_latest_blockhash().and_then(|blockhash| {
+            client.get_fee_for_message(&Message::new_with_blockhash(
+                &[],
+                Some(&client.payer_pubkey()),
+                &blockhash,
+            ))
+        }) {
+            Err(_err) => return vec![],
+            Ok(lamports_per_signature) => lamports_per_signature,
+        };
+
+        // Order the crankable queues by their fee bids, highest first.
+        let mut queue_pubkeys = self
+            .crankable_queues
             .iter()
-            .filter_map(|queue_pubkey_ref| {
-                self.clone()
-                    .build_crank_tx(client.clone(), *queue_pubkey_ref.key())
-                    .ok()
+            .map(|queue_pubkey_ref| *queue_pubkey_ref.key())
+            .collect::<Vec<Pubkey>>();
+        queue_pubkeys.sort_by_cached_key(|queue_pubkey| {
+            std::cmp::Reverse(self.fee_bids.get(queue_pubkey).map_or(0, |v| *v.value()))
+        });
+
+        // Build the set of crank transactions, until the time limit is reached.
+        // TODO Use rayon to parallelize this operation
+        let now = std::time::Instant::now();
+        queue_pubkeys
+            .into_iter()
+            .take_while(|_queue_pubkey| now.elapsed().as_millis() < CRANK_BUILD_TIME_LIMIT)
+            .filter_map(|queue_pubkey| {
+                let tx = self.clone().build_crank_tx(
+                    client.clone(),
+                    lamports_per_signature,
+                    &pool_positions,
+                    queue_pubkey,
+                    slot,
+                    worker_pool,
+                );
+
+                // If a condition queue's predicate was not met, back off before polling it again.
+                if tx.is_err() {
+                    if let Some(mut condition_poll) = self.condition_queues.get_mut(&queue_pubkey) {
+                        self.crankable_queues.remove(&queue_pubkey);
+                        condition_poll.back_off(slot);
+                    }
+                }
+
+                tx.ok()
             })
-            .collect::<Vec<Transaction>>()
+            .collect::<Vec<VersionedTransaction>>()
     }
 
     pub fn build_crank_tx(
         self: Arc<Self>,
         client: Arc<ClockworkClient>,
+        lamports_per_signature: u64,
+        pool_positions: &HashMap<Pubkey, PoolPosition>,
         queue_pubkey: Pubkey,
-    ) -> PluginResult<Transaction> {
+        slot: u64,
+        worker_pool: Pubkey,
+    ) -> PluginResult<VersionedTransaction> {
         // Build the first crank ix
-        let queue = client.get::<Queue>(&queue_pubkey).unwrap();
+        let queue = client
+            .get::<Queue>(&queue_pubkey)
+            .map_err(|_err| GeyserPluginError::Custom("Failed to get queue".into()))?;
+
+        // Exit early if this node is not a worker in the queue's pool.
+        let pool_pubkey = queue.pool.unwrap_or(worker_pool);
+        if let Some(pool_position) = pool_positions.get(&pool_pubkey) {
+            if pool_position.current_position.is_none() && !pool_position.workers.is_empty() {
+                return Err(GeyserPluginError::Custom(
+                    "This node is not an authorized worker".into(),
+                ));
+            }
+        }
+
+        // Exit early if the queue is backing off from a failed crank.
+        if let Some(retry_at) = queue.retry_at() {
+            if slot < retry_at {
+                return Err(GeyserPluginError::Custom(
+                    "Queue is waiting to retry a failed crank".into(),
+                ));
+            }
+        }
+
         let blockhash = client
             .get_latest_blockhash()
             .map_err(|_err| GeyserPluginError::Custom("Failed to get latest blockhash".into()))?;
         let worker_pubkey = client.payer_pubkey();
 
+        // Get the address lookup tables to load the crank accounts from.
+        let address_lookup_table_accounts = queue
+            .address_lookup_tables
+            .iter()
+            .map(|pubkey| client.get_address_lookup_table(pubkey))
+            .collect::<ClientResult<Vec<AddressLookupTableAccount>>>()
+            .map_err(|_err| {
+                GeyserPluginError::Custom("Failed to get address lookup tables".into())
+            })?;
+
+        // Build the compute budget ixs to prepend to the tx.
+        let compute_budget_ixs = build_compute_budget_ixs(&queue);
+        let compute_unit_limit = queue
+            .compute_unit_limit
+            .map_or(COMPUTE_BUDGET_LIMIT, |compute_unit_limit| {
+                compute_unit_limit as u64
+            });
+
         // Pre-simulate crank ixs and pack into tx
-        let mut ixs: Vec<Instruction> =
-            vec![self
-                .clone()
-                .build_crank_ix(client.clone(), queue, worker_pubkey)?];
+        let mut ixs: Vec<Instruction> = vec![self.clone().build_crank_ix(
+            client.clone(),
+            lamports_per_signature,
+            pool_pubkey,
+            queue.clone(),
+            worker_pubkey,
+        )?];
 
         // Pre-simulate crank ixs and pack as many as possible into tx.
-        let mut tx: Transaction = Transaction::new_with_payer(&vec![], Some(&worker_pubkey));
+        let mut tx: Option<VersionedTransaction> = None;
         let now = std::time::Instant::now();
         loop {
-            let mut sim_tx = Transaction::new_with_payer(&ixs, Some(&worker_pubkey));
-            sim_tx.sign(&[client.payer()], blockhash);
+            let sim_tx = build_tx(
+                client.payer(),
+                &[compute_budget_ixs.clone(), ixs.clone()].concat(),
+                &address_lookup_table_accounts,
+                blockhash,
+            )?;
 
             // Exit early if tx exceeds Solana's size limit.
-            // TODO With QUIC and Transaction v2 lookup tables, Solana will soon support much larger transaction sizes.
-            if sim_tx.message_data().len() > TRANSACTION_SIZE_LIMIT {
+            let message_len = sim_tx.message.serialize().len();
+            if message_len > TRANSACTION_SIZE_LIMIT {
                 info!(
                     "Transaction message exceeded size limit with {} bytes",
-                    sim_tx.message_data().len()
+                    message_len
                 );
                 break;
             }
 
             // Simulate the complete packed tx.
-            match client.simulate_transaction_with_config(
+            match client.simulate_versioned_transaction_with_config(
                 &sim_tx,
                 RpcSimulateTransactionConfig {
                     replace_recent_blockhash: true,
@@ -287,29 +651,69 @@ impl QueueObserver {
                 Ok(response) => {
                     // If there was an error, then stop packing.
                     if response.value.err.is_some() {
+                        // If the first crank failed inside one of the queue's instructions, report the failure instead.
+                        let failed_in_inner_ix = match response.value.logs.as_deref() {
+                            None => false,
+                            Some(logs) => is_inner_ix_failure(
+                                logs,
+                                &get_inner_ix_program_ids(&client, &queue)?,
+                            ),
+                        };
+                        if ixs.len() == 1 && failed_in_inner_ix {
+                            let fail_tx = build_tx(
+                                client.payer(),
+                                &[build_fail_ix(
+                                    &client,
+                                    response.context.slot,
+                                    pool_pubkey,
+                                    queue,
+                                    worker_pubkey,
+                                )?],
+                                &[],
+                                blockhash,
+                            )?;
+
+                            // If the failure cannot be reported either, stop retrying the queue until it is next updated.
+                            let is_reportable = matches!(
+                                client.simulate_versioned_transaction_with_config(
+                                    &fail_tx,
+                                    RpcSimulateTransactionConfig {
+                                        replace_recent_blockhash: true,
+                                        commitment: Some(CommitmentConfig::processed()),
+                                        ..RpcSimulateTransactionConfig::default()
+                                    },
+                                ),
+                                Ok(response) if response.value.err.is_none()
+                            );
+                            if !is_reportable {
+                                self.crankable_queues.remove(&queue_pubkey);
+                                return Err(GeyserPluginError::Custom(
+                                    "Failed to report the queue's failure".into(),
+                                ));
+                            }
+                            return Ok(fail_tx);
+                        }
                         break;
                     }
 
                     // If the compute budget limit was exceeded, then stop packing.
-                    if response
-                        .value
-                        .units_consumed
-                        .ge(&Some(COMPUTE_BUDGET_LIMIT))
-                    {
+                    if response.value.units_consumed.ge(&Some(compute_unit_limit)) {
                         break;
                     }
 
                     // Save the simulated tx. It is okay to submit.
-                    tx = sim_tx;
+                    tx = Some(sim_tx);
 
                     // Parse the resulting queue account for the next crank ix to simulate.
                     if let Some(ui_accounts) = response.value.accounts {
                         if let Some(Some(ui_account)) = ui_accounts.get(0) {
                             if let Some(account) = ui_account.decode::<Account>() {
                                 if let Ok(sim_queue) = Queue::try_from(account.data) {
-                                    if sim_queue.next_instruction.is_some() {
+                                    if !sim_queue.next_instructions.is_empty() {
                                         ixs.push(self.clone().build_crank_ix(
                                             client.clone(),
+                                            lamports_per_signature,
+                                            pool_pubkey,
                                             sim_queue,
                                             worker_pubkey,
                                         )?);
@@ -326,98 +730,107 @@ impl QueueObserver {
 
         info!("Time spent packing cranks: {:#?}", now.elapsed());
 
-        if tx.message.instructions.len() == 0 {
-            return Err(GeyserPluginError::Custom(
-                "Transaction has no instructions".into(),
-            ));
-        }
-
-        Ok(tx)
+        tx.ok_or(GeyserPluginError::Custom(
+            "Transaction has no instructions".into(),
+        ))
     }
 
     fn build_crank_ix(
         self: Arc<Self>,
         client: Arc<ClockworkClient>,
+        lamports_per_signature: u64,
+        pool_pubkey: Pubkey,
         queue: Queue,
         worker_pubkey: Pubkey,
     ) -> PluginResult<Instruction> {
-        // TODO If this queue is an account listener, grab the account and create the data_hash.
-        let mut trigger_account_pubkey: Option<Pubkey> = None;
-        let mut data_hash: Option<u64> = None;
-        match queue.trigger {
-            Trigger::Account { pubkey } => {
-                // Save the trigger account.
-                trigger_account_pubkey = Some(pubkey);
-
-                // Begin computing the data hash of this account.
-                let data = client.get_account_data(&pubkey).unwrap();
-                let mut hasher = DefaultHasher::new();
-                data.hash(&mut hasher);
-
-                // Check the exec context for the prior data hash.
-                match queue.exec_context.clone() {
-                    None => {
-                        // This queue has not begun executing yet.
-                        // There is no prior data hash to include in our hash.
-                        data_hash = Some(hasher.finish());
-                    }
-                    Some(exec_context) => {
-                        match exec_context.trigger_context {
-                            TriggerContext::Account {
-                                data_hash: prior_data_hash,
-                            } => {
-                                // Inject the prior data hash as a seed.
-                                prior_data_hash.hash(&mut hasher);
-                                data_hash = Some(hasher.finish());
-                            }
-                            _ => {
-                                return Err(GeyserPluginError::Custom("Invalid queue state".into()))
-                            }
-                        }
-                    }
-                };
-            }
-            _ => {}
-        }
+        // Get the data hash and accounts to verify the queue's trigger with.
+        let (data_hash, trigger_account_pubkeys) = get_trigger_accounts(&client, &queue)?;
 
         // Build the instruction.
         let queue_pubkey = Queue::pubkey(queue.authority, queue.id);
-        let inner_ix = queue
-            .next_instruction
-            .clone()
-            .unwrap_or(queue.kickoff_instruction);
+        let kickoff_predicate = match queue.trigger {
+            Trigger::Condition { predicate } if queue.next_instructions.is_empty() => {
+                Some(predicate)
+            }
+            _ => None,
+        };
+        let inner_ixs = if queue.next_instructions.is_empty() {
+            queue.kickoff_instructions
+        } else {
+            queue.next_instructions
+        };
         let mut crank_ix = clockwork_client::queue::instruction::queue_crank(
             data_hash,
+            lamports_per_signature,
+            pool_pubkey,
             queue_pubkey,
             worker_pubkey,
         );
 
-        // Inject the trigger account.
-        match trigger_account_pubkey {
-            None => {}
-            Some(pubkey) => crank_ix.accounts.push(AccountMeta {
+        // Inject the trigger accounts.
+        for pubkey in trigger_account_pubkeys {
+            crank_ix.accounts.push(AccountMeta {
                 pubkey,
                 is_signer: false,
                 is_writable: false,
-            }),
+            });
         }
 
-        // Inject the target program account to the ix.
-        crank_ix
-            .accounts
-            .push(AccountMeta::new_readonly(inner_ix.program_id, false));
+        // If the queue has sponsors, inject its funding account in case this crank closes the queue.
+        if queue.sponsored_lamports > 0 {
+            crank_ix.accounts.push(AccountMeta::new(
+                Funding::pubkey(queue_pubkey, queue.created_at.slot),
+                false,
+            ));
+        }
 
-        // Inject the worker pubkey as the Clockwork "payer" account
-        for acc in inner_ix.clone().accounts {
-            let acc_pubkey = if acc.pubkey == clockwork_client::queue::utils::PAYER_PUBKEY {
-                worker_pubkey
-            } else {
-                acc.pubkey
-            };
-            crank_ix.accounts.push(match acc.is_writable {
-                true => AccountMeta::new(acc_pubkey, false),
-                false => AccountMeta::new_readonly(acc_pubkey, false),
-            })
+        // If the queue will auto-close once it expires, inject its authority to close it to, and the authority's
+        // token account to sweep its tokens to.
+        if queue.auto_close {
+            crank_ix
+                .accounts
+                .push(AccountMeta::new(queue.authority, false));
+            if let Some(mint) = queue.fee_mint {
+                crank_ix.accounts.push(AccountMeta::new(
+                    get_associated_token_address(&queue.authority, &mint),
+                    false,
+                ));
+            }
+        }
+
+        // If the queue pays crank fees in tokens, inject its token account, the token accounts the fee may be
+        // escrowed to, and the token program.
+        if let Some(mint) = queue.fee_mint {
+            let fee_tokens_pubkey =
+                get_associated_token_address(&Fee::pubkey(worker_pubkey), &mint);
+            crank_ix.accounts.append(&mut vec![
+                AccountMeta::new(get_associated_token_address(&queue_pubkey, &mint), false),
+                AccountMeta::new(fee_tokens_pubkey, false),
+                AccountMeta::new(
+                    get_associated_token_address(&Config::pubkey(), &mint),
+                    false,
+                ),
+                AccountMeta::new_readonly(spl_token::ID, false),
+            ]);
+
+            // If this worker does not have a token account to escrow its fees in yet, inject the accounts for the
+            // crank to create it with.
+            if client.get_account(&fee_tokens_pubkey).is_err() {
+                crank_ix.accounts.append(&mut vec![
+                    AccountMeta::new_readonly(mint, false),
+                    AccountMeta::new_readonly(spl_associated_token_account::ID, false),
+                    AccountMeta::new_readonly(sysvar::rent::ID, false),
+                ]);
+            }
+        }
+
+        // If this crank will kickoff a condition queue, inject the accounts for its predicate.
+        if let Some(predicate) = kickoff_predicate {
+            push_inner_ix_accounts(&client, &mut crank_ix, predicate, worker_pubkey)?;
+        }
+
+        for inner_ix in inner_ixs {
+            push_inner_ix_accounts(&client, &mut crank_ix, inner_ix, worker_pubkey)?;
         }
 
         Ok(crank_ix)
@@ -434,6 +847,58 @@ impl QueueObserver {
         self.runtime.spawn(f(self.clone()));
         Ok(())
     }
+
+    // Drop an expired queue from the trigger indexes. Queues which will auto-close are moved into the crankable set.
+    fn expire_queue(&self, queue_pubkey: Pubkey, auto_close: bool) {
+        self.unindex_queue(&queue_pubkey);
+        if auto_close {
+            self.crankable_queues.insert(queue_pubkey);
+        }
+    }
+
+    // Index a queue as listening for updates to an account.
+    fn index_listener(
+        &self,
+        account_pubkey: Pubkey,
+        listener: AccountListener,
+        queue_pubkey: Pubkey,
+    ) {
+        self.listener_queues
+            .entry(account_pubkey)
+            .and_modify(|v| {
+                v.insert(queue_pubkey, listener);
+            })
+            .or_insert_with(|| {
+                let v = DashMap::new();
+                v.insert(queue_pubkey, listener);
+                v
+            });
+    }
+
+    // Remove a queue from all of the trigger indexes.
+    fn unindex_queue(&self, queue_pubkey: &Pubkey) {
+        self.condition_queues.remove(queue_pubkey);
+        self.cron_queues.retain(|_target_timestamp, queue_pubkeys| {
+            queue_pubkeys.remove(queue_pubkey);
+            !queue_pubkeys.is_empty()
+        });
+        self.dependent_queues.retain(|_queue_pubkey, dependents| {
+            dependents.remove(queue_pubkey);
+            !dependents.is_empty()
+        });
+        self.epoch_queues.retain(|_target_epoch, queue_pubkeys| {
+            queue_pubkeys.remove(queue_pubkey);
+            !queue_pubkeys.is_empty()
+        });
+        self.listener_queues.retain(|_account_pubkey, listeners| {
+            listeners.remove(queue_pubkey);
+            !listeners.is_empty()
+        });
+        self.slot_queues.retain(|_target_slot, queue_pubkeys| {
+            queue_pubkeys.remove(queue_pubkey);
+            !queue_pubkeys.is_empty()
+        });
+    }
 }
 
 impl Debug for QueueObserver {
@@ -442,13 +907,276 @@ impl Debug for QueueObserver {
     }
 }
 
-fn next_moment(after: i64, schedule: String) -> Option<i64> {
-    Schedule::from_str(&schedule)
-        .unwrap()
-        .next_after(&DateTime::<Utc>::from_utc(
-            NaiveDateTime::from_timestamp(after, 0),
-            Utc,
-        ))
-        .take()
-        .map(|datetime| datetime.timestamp())
+fn build_compute_budget_ixs(queue: &Queue) -> Vec<Instruction> {
+    let mut ixs = vec![];
+
+    // Request the queue's compute unit limit. Queues which pay a priority fee without setting a limit
+    // request the maximum limit.
+    match (queue.compute_unit_limit, queue.compute_unit_price) {
+        (Some(compute_unit_limit), _) => {
+            ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(
+                compute_unit_limit,
+            ));
+        }
+        (None, Some(_)) => {
+            ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(
+                MAX_COMPUTE_UNIT_LIMIT,
+            ));
+        }
+        (None, None) => {}
+    }
+
+    // Set the queue's priority fee.
+    if let Some(compute_unit_price) = queue.compute_unit_price {
+        ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
+            compute_unit_price,
+        ));
+    }
+
+    ixs
+}
+
+// Builds a signed crank tx. Queues with address lookup tables are cranked with v0 transactions which load
+// their accounts from the tables, and all others with legacy transactions.
+fn build_tx(
+    payer: &Keypair,
+    ixs: &[Instruction],
+    address_lookup_table_accounts: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> PluginResult<VersionedTransaction> {
+    if address_lookup_table_accounts.is_empty() {
+        let mut tx = Transaction::new_with_payer(ixs, Some(&payer.pubkey()));
+        tx.sign(&[payer], blockhash);
+        return Ok(tx.into());
+    }
+    let message = compile_v0_message(
+        &payer.pubkey(),
+        ixs,
+        address_lookup_table_accounts,
+        blockhash,
+    )
+    .ok_or(GeyserPluginError::Custom(
+        "Failed to compile v0 message".into(),
+    ))?;
+    VersionedTransaction::try_new(VersionedMessage::V0(message), &[payer])
+        .map_err(|_err| GeyserPluginError::Custom("Failed to sign transaction".into()))
+}
+
+fn build_fail_ix(
+    client: &ClockworkClient,
+    attempted_at: u64,
+    pool_pubkey: Pubkey,
+    queue: Queue,
+    worker_pubkey: Pubkey,
+) -> PluginResult<Instruction> {
+    let (data_hash, trigger_account_pubkeys) = get_trigger_accounts(client, &queue)?;
+    let mut fail_ix = clockwork_client::queue::instruction::queue_fail(
+        attempted_at,
+        data_hash,
+        pool_pubkey,
+        Queue::pubkey(queue.authority, queue.id.clone()),
+        worker_pubkey,
+    );
+
+    // The program only accepts failures for queues that are due to be cranked. If the failing crank would have
+    // kicked off a new thread, inject the accounts to verify the queue's trigger with. Condition predicates are
+    // not re-run, since the predicate may be the instruction which failed.
+    if queue.next_instructions.is_empty() {
+        for pubkey in trigger_account_pubkeys {
+            fail_ix
+                .accounts
+                .push(AccountMeta::new_readonly(pubkey, false));
+        }
+    }
+
+    Ok(fail_ix)
+}
+
+// Gets the program ids of the instructions a crank will invoke on behalf of the queue, in order: its predicate, if
+// the crank will kick off a condition queue, and then its kickoff or next instructions.
+fn get_inner_ix_program_ids(client: &ClockworkClient, queue: &Queue) -> PluginResult<Vec<Pubkey>> {
+    let mut inner_ixs = vec![];
+    if queue.next_instructions.is_empty() {
+        if let Trigger::Condition { predicate } = queue.trigger.clone() {
+            inner_ixs.push(predicate);
+        }
+        inner_ixs.extend(queue.kickoff_instructions.clone());
+    } else {
+        inner_ixs.extend(queue.next_instructions.clone());
+    }
+    inner_ixs
+        .into_iter()
+        .map(|inner_ix| match inner_ix {
+            QueueInstruction::Buffered { buffer } => {
+                get_buffered_ix(client, buffer).map(|ix| ix.program_id)
+            }
+            QueueInstruction::Inline(inner_ix) => Ok(inner_ix.program_id),
+        })
+        .collect()
+}
+
+// Gets the data hash of the watched bytes of an account trigger, and the accounts to verify a queue's trigger with.
+fn get_trigger_accounts(
+    client: &ClockworkClient,
+    queue: &Queue,
+) -> PluginResult<(Option<u64>, Vec<Pubkey>)> {
+    match queue.trigger.clone() {
+        Trigger::Account {
+            pubkey,
+            offset,
+            size,
+        } => {
+            // Compute the data hash of the watched bytes.
+            let data = client.get_account_data(&pubkey).map_err(|_err| {
+                GeyserPluginError::Custom("Failed to get trigger account data".into())
+            })?;
+            Ok((Some(account_data_hash(&data, offset, size)), vec![pubkey]))
+        }
+        Trigger::Accounts { accounts, mode: _ } => {
+            // The program hashes the data of the trigger accounts itself.
+            Ok((
+                None,
+                accounts
+                    .iter()
+                    .map(|watched_account| watched_account.pubkey)
+                    .collect(),
+            ))
+        }
+        Trigger::Queue { queue } => {
+            // The followed queue is the trigger account.
+            Ok((None, vec![queue]))
+        }
+        _ => Ok((None, vec![])),
+    }
+}
+
+// Gets the instruction held in an instruction buffer.
+fn get_buffered_ix(
+    client: &ClockworkClient,
+    buffer_pubkey: Pubkey,
+) -> PluginResult<InstructionData> {
+    let data = client
+        .get_account_data(&buffer_pubkey)
+        .map_err(|_err| GeyserPluginError::Custom("Failed to get instruction buffer".into()))?;
+    InstructionBuffer::try_parse(&data)
+        .map(|(_buffer, instruction)| instruction)
+        .map_err(|_err| GeyserPluginError::Custom("Failed to parse buffered instruction".into()))
+}
+
+fn push_inner_ix_accounts(
+    client: &ClockworkClient,
+    ix: &mut Instruction,
+    inner_ix: QueueInstruction,
+    worker_pubkey: Pubkey,
+) -> PluginResult<()> {
+    // If the inner ix is held in an instruction buffer, inject the buffer account and load the buffered ix.
+    let inner_ix = match inner_ix {
+        QueueInstruction::Buffered { buffer } => {
+            ix.accounts.push(AccountMeta::new_readonly(buffer, false));
+            get_buffered_ix(client, buffer)?
+        }
+        QueueInstruction::Inline(inner_ix) => inner_ix,
+    };
+
+    // Inject the target program account to the ix.
+    ix.accounts
+        .push(AccountMeta::new_readonly(inner_ix.program_id, false));
+
+    // Inject the worker pubkey as the Clockwork "payer" account
+    for acc in inner_ix.accounts {
+        let acc_pubkey = if acc.pubkey == clockwork_client::queue::utils::PAYER_PUBKEY {
+            worker_pubkey
+        } else {
+            acc.pubkey
+        };
+        ix.accounts.push(match acc.is_writable {
+            true => AccountMeta::new(acc_pubkey, false),
+            false => AccountMeta::new_readonly(acc_pubkey, false),
+        })
+    }
+
+    Ok(())
+}
+
+// Whether a simulated crank failed inside one of the queue's own instructions: its kickoff predicate or the
+// instructions being cranked, whose program ids are given in the order they are invoked. Failures in the queue
+// program's own checks, or in the instructions it invokes itself, such as creating the worker's fee account or
+// transferring a token crank fee, are not the queue's instructions failing. Those cranks are simply left unsent.
+fn is_inner_ix_failure(logs: &[String], inner_ix_program_ids: &[Pubkey]) -> bool {
+    let mut in_crank = false;
+    let mut in_inner_ix = false;
+    let mut inner_ix_index = 0;
+    for log in logs {
+        let mut words = log.split_whitespace();
+        let (program_id, action, depth) = match (
+            words.next(),
+            words.next().and_then(|word| Pubkey::from_str(word).ok()),
+            words.next(),
+        ) {
+            (Some("Program"), Some(program_id), Some(action)) => (program_id, action, words.next()),
+            _ => continue,
+        };
+        match (action, depth) {
+            ("invoke", Some("[1]")) => in_crank = program_id.eq(&clockwork_client::queue::ID),
+            ("invoke", Some("[2]")) if in_crank => {
+                in_inner_ix = inner_ix_program_ids.get(inner_ix_index) == Some(&program_id);
+                if in_inner_ix {
+                    inner_ix_index += 1;
+                }
+            }
+            ("failed:", _) => {
+                return in_crank && in_inner_ix && program_id.ne(&clockwork_client::queue::ID)
+            }
+            _ => {}
+        }
+    }
+    false
+}
+
+/// A queue listening for updates to a range of bytes in an account's data.
+#[derive(Clone, Copy, Debug)]
+pub struct AccountListener {
+    // The data hash of the watched bytes at the queue's last execution.
+    pub data_hash: Option<u64>,
+
+    // Whether the queue is triggered by a change to this account alone, or only once all of its accounts have changed.
+    pub mode: AccountsTriggerMode,
+
+    // The byte offset of the watched range.
+    pub offset: Option<u64>,
+
+    // The byte size of the watched range.
+    pub size: Option<u64>,
+}
+
+/// The schedule a condition queue's predicate is polled on. Polling backs off while the predicate is false.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ConditionPoll {
+    // The number of consecutive polls which found the predicate to be false.
+    pub failed_polls: u32,
+
+    // The slot at which the predicate is next polled.
+    pub poll_at: u64,
+}
+
+impl ConditionPoll {
+    // Record a failed poll, doubling the number of slots to wait before the next one.
+    fn back_off(&mut self, slot: u64) {
+        self.failed_polls = self.failed_polls.saturating_add(1);
+        let backoff = 2u64
+            .checked_pow(self.failed_polls)
+            .unwrap_or(CONDITION_POLL_BACKOFF_LIMIT)
+            .min(CONDITION_POLL_BACKOFF_LIMIT);
+        self.poll_at = slot.saturating_add(backoff);
+    }
+}
+
+/// The expiry settings of a queue with an expiration time.
+#[derive(Clone, Copy, Debug)]
+pub struct QueueExpiry {
+    // Whether or not the queue will be closed once it has expired.
+    pub auto_close: bool,
+
+    // The unix timestamp at which the queue expires.
+    pub expires_at: i64,
 }
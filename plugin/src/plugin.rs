@@ -84,14 +84,21 @@ impl GeyserPlugin for ClockworkPlugin {
                         slot,
                     )
                 }
-                AccountUpdateEvent::Pool { pool } => {
-                    self.observers.pool.clone().handle_updated_pool(pool, slot)
-                }
+                AccountUpdateEvent::Pool { pool } => self
+                    .observers
+                    .pool
+                    .clone()
+                    .handle_updated_pool(pool, account_pubkey, slot),
                 AccountUpdateEvent::Queue { queue } => self
                     .observers
                     .queue
                     .clone()
                     .handle_updated_queue(queue, account_pubkey),
+                AccountUpdateEvent::QueueClosed => self
+                    .observers
+                    .queue
+                    .clone()
+                    .handle_closed_queue(account_pubkey),
                 AccountUpdateEvent::Rotator { rotator } => {
                     self.observers.pool.clone().handle_updated_rotator(rotator)
                 }
@@ -97,5 +97,8 @@ pub fn handler(ctx: Context<EntryClose>) -> Result<CrankResponse> {
         None
     };
 
-    Ok(CrankResponse { next_instruction })
+    Ok(CrankResponse {
+        next_instructions: next_instruction.into_iter().collect(),
+        ..CrankResponse::default()
+    })
 }
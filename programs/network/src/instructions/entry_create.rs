@@ -132,5 +132,8 @@ pub fn handler(ctx: Context<EntryCreate>) -> Result<CrankResponse> {
         )
     };
 
-    Ok(CrankResponse { next_instruction })
+    Ok(CrankResponse {
+        next_instructions: next_instruction.into_iter().collect(),
+        ..CrankResponse::default()
+    })
 }
@@ -5,7 +5,7 @@ use {
         solana_program::{instruction::Instruction, system_program},
     },
     anchor_spl::token::Mint,
-    clockwork_queue_program::objects::{Queue, Trigger},
+    clockwork_queue_program::objects::{Queue, QueueCreateSettings, Trigger},
     std::mem::size_of,
 };
 
@@ -23,6 +23,9 @@ pub struct Initialize<'info> {
     )]
     pub authority: Account<'info, Authority>,
 
+    #[account(address = clockwork_queue_program::objects::Config::pubkey())]
+    pub clockwork_config: Account<'info, clockwork_queue_program::objects::Config>,
+
     #[account(address = clockwork_queue_program::ID)]
     pub clockwork_program: Program<'info, clockwork_queue_program::program::QueueProgram>,
 
@@ -79,6 +82,7 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Initialize<'info>>) -> Res
     // Get accounts
     let admin = &ctx.accounts.admin;
     let authority = &ctx.accounts.authority;
+    let clockwork_config = &ctx.accounts.clockwork_config;
     let clockwork_program = &ctx.accounts.clockwork_program;
     let config = &mut ctx.accounts.config;
     let rotator = &mut ctx.accounts.rotator;
@@ -113,17 +117,26 @@ pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, Initialize<'info>>) -> Res
             clockwork_program.to_account_info(),
             clockwork_queue_program::cpi::accounts::QueueCreate {
                 authority: authority.to_account_info(),
+                config: clockwork_config.to_account_info(),
                 payer: admin.to_account_info(),
                 queue: snapshot_queue.to_account_info(),
                 system_program: system_program.to_account_info(),
             },
             &[&[SEED_AUTHORITY, &[bump]]],
         ),
-        "snapshot".into(),
-        snapshot_kickoff_ix.into(),
-        Trigger::Cron {
-            schedule: "0 * * * * * *".into(),
-            skippable: true,
+        QueueCreateSettings {
+            auto_close: false,
+            exec_history_capacity: 0,
+            expires_at: None,
+            id: "snapshot".into(),
+            kickoff_instructions: vec![snapshot_kickoff_ix.into()],
+            max_executions: None,
+            pool: None,
+            trigger: Trigger::Cron {
+                schedule: "0 * * * * * *".into(),
+                skippable: true,
+                timezone: None,
+            },
         },
     )?;
 
@@ -69,5 +69,8 @@ pub fn handler(ctx: Context<SnapshotClose>) -> Result<CrankResponse> {
         None
     };
 
-    Ok(CrankResponse { next_instruction })
+    Ok(CrankResponse {
+        next_instructions: next_instruction.into_iter().collect(),
+        ..CrankResponse::default()
+    })
 }
@@ -101,5 +101,8 @@ pub fn handler(ctx: Context<SnapshotCreate>) -> Result<CrankResponse> {
         )
     };
 
-    Ok(CrankResponse { next_instruction })
+    Ok(CrankResponse {
+        next_instructions: next_instruction.into_iter().collect(),
+        ..CrankResponse::default()
+    })
 }
@@ -29,21 +29,20 @@ pub fn handler(ctx: Context<SnapshotKickoff>) -> Result<CrankResponse> {
 
     // Build the next crank instruction
     Ok(CrankResponse {
-        next_instruction: Some(
-            Instruction {
-                program_id: crate::ID,
-                accounts: vec![
-                    AccountMeta::new_readonly(authority.key(), false),
-                    AccountMeta::new_readonly(Config::pubkey(), false),
-                    AccountMeta::new(clockwork_queue_program::utils::PAYER_PUBKEY, true),
-                    AccountMeta::new(registry.key(), false),
-                    AccountMeta::new(Snapshot::pubkey(registry.snapshot_count), false),
-                    AccountMeta::new_readonly(snapshot_queue.key(), true),
-                    AccountMeta::new_readonly(system_program::ID, false),
-                ],
-                data: clockwork_queue_program::utils::anchor_sighash("snapshot_create").into(),
-            }
-            .into(),
-        ),
+        next_instructions: vec![Instruction {
+            program_id: crate::ID,
+            accounts: vec![
+                AccountMeta::new_readonly(authority.key(), false),
+                AccountMeta::new_readonly(Config::pubkey(), false),
+                AccountMeta::new(clockwork_queue_program::utils::PAYER_PUBKEY, true),
+                AccountMeta::new(registry.key(), false),
+                AccountMeta::new(Snapshot::pubkey(registry.snapshot_count), false),
+                AccountMeta::new_readonly(snapshot_queue.key(), true),
+                AccountMeta::new_readonly(system_program::ID, false),
+            ],
+            data: clockwork_queue_program::utils::anchor_sighash("snapshot_create").into(),
+        }
+        .into()],
+        ..CrankResponse::default()
     })
 }
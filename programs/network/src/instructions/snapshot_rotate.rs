@@ -71,5 +71,8 @@ pub fn handler(ctx: Context<SnapshotRotate>) -> Result<CrankResponse> {
         .into(),
     );
 
-    Ok(CrankResponse { next_instruction })
+    Ok(CrankResponse {
+        next_instructions: next_instruction.into_iter().collect(),
+        ..CrankResponse::default()
+    })
 }
@@ -3,6 +3,8 @@
 use anchor_lang::prelude::*;
 
 /// Errors for the the Clockwork queue program.
+///
+/// Error codes are assigned by position, so new variants must be appended to the end.
 #[error_code]
 pub enum ClockworkError {
     /// Thrown if a crank response has an invalid program ID or cannot be parsed.
@@ -32,4 +34,104 @@ pub enum ClockworkError {
     /// Thrown if an inner instruction attempted to write to an unauthorized address.
     #[msg("Inner instruction attempted to write to an unauthorized address")]
     UnauthorizedWrite,
+
+    /// Thrown if a value provided for execution history capacity exceeds the maximum allowed value.
+    #[msg("The value provided for execution history capacity exceeds the maximum allowed value")]
+    ExecHistoryCapacityTooLarge,
+
+    /// Thrown if a failure is reported for a queue before its retry backoff has elapsed.
+    #[msg("The queue's retry backoff has not elapsed")]
+    InvalidRetry,
+
+    /// Thrown if a request requires an authorized worker and the signer is not in the worker pool.
+    #[msg("The worker is not in the worker pool")]
+    UnauthorizedWorker,
+
+    /// Thrown if a request would start a new thread execution on a queue which has expired.
+    #[msg("The queue has expired")]
+    QueueExpired,
+
+    /// Thrown if a cron trigger's timezone cannot be parsed.
+    #[msg("The cron trigger's timezone could not be parsed")]
+    InvalidTimezone,
+
+    /// Thrown if an instruction data placeholder cannot be filled.
+    #[msg("The instruction data placeholder could not be filled")]
+    InvalidPlaceholder,
+
+    /// Thrown if a fee bid is below the config's crank fee.
+    #[msg("The fee bid is below the minimum crank fee")]
+    FeeBidTooLow,
+
+    /// Thrown if a value provided for compute unit limit exceeds the maximum allowed value.
+    #[msg("The value provided for compute unit limit exceeds the maximum allowed value")]
+    ComputeUnitLimitTooLarge,
+
+    /// Thrown if an execution rate limit allows no executions or has an empty window.
+    #[msg("The execution rate limit is invalid")]
+    InvalidExecRateLimit,
+
+    /// Thrown if a debit would leave a queue below its rent-exempt minimum balance.
+    #[msg("The queue has insufficient funds")]
+    InsufficientFunds,
+
+    /// Thrown if a sponsored queue is closed without its funding account.
+    #[msg("The queue's funding account is missing or invalid")]
+    InvalidFundingAccount,
+
+    /// Thrown if a queue is funded after it has been deleted, or a sponsor reclaims before it has been deleted.
+    #[msg("The funding account is in an invalid state")]
+    InvalidFundingState,
+
+    /// Thrown if a sponsor cannot be found in a queue's funding account.
+    #[msg("The sponsor has not funded this queue")]
+    SponsorNotFound,
+
+    /// Thrown if a fee mint is not whitelisted by the config.
+    #[msg("The fee mint is not whitelisted")]
+    InvalidFeeMint,
+
+    /// Thrown if a token account required to pay or withhold a crank fee in tokens is missing.
+    #[msg("A token account required to pay the crank fee is missing")]
+    InvalidTokenAccount,
+
+    /// Thrown if a write would extend past the end of an instruction buffer.
+    #[msg("The write does not fit in the instruction buffer")]
+    InstructionBufferTooSmall,
+
+    /// Thrown if a buffered instruction's buffer account is missing, is owned by another authority, or cannot be parsed.
+    #[msg("The instruction buffer is missing or invalid")]
+    InvalidInstructionBuffer,
+
+    /// Thrown if an accounts trigger watches no accounts or more than the maximum number of accounts.
+    #[msg("The trigger watches too few or too many accounts")]
+    InvalidTriggerAccounts,
+
+    /// Thrown if a claim exceeds the balance or withholding held by a fee account.
+    #[msg("The fee account has insufficient balance")]
+    InsufficientFeeBalance,
+
+    /// Thrown if the account a queue is to be closed to is not among the accounts passed to the crank.
+    #[msg("The account to close the queue to is missing")]
+    InvalidCloseToAccount,
+
+    /// Thrown if an instruction buffer would exceed the size of an account the program may create.
+    #[msg("The instruction buffer is too large")]
+    InstructionBufferTooLarge,
+
+    /// Thrown if a queue names a worker pool which is not a pool account, or which is not passed to the instruction.
+    #[msg("The worker pool is invalid")]
+    InvalidPool,
+
+    /// Thrown if a failure is reported for a crank attempt which is too old, or which a successful crank has followed.
+    #[msg("The failed crank attempt is invalid")]
+    InvalidFailedAttempt,
+
+    /// Thrown if a crank transaction includes instructions other than cranks which require signatures.
+    #[msg("The crank transaction includes other signed instructions")]
+    InvalidCrankTransaction,
+
+    /// Thrown if a queue to migrate does not have the original queue layout.
+    #[msg("The queue does not have the original layout")]
+    InvalidLegacyQueue,
 }
@@ -0,0 +1,158 @@
+//! Events emitted by the program.
+
+use {
+    crate::objects::{Trigger, TriggerContext},
+    anchor_lang::prelude::*,
+};
+
+/// Emitted when a sponsor reclaims their share of a deleted queue's funding.
+#[event]
+#[derive(Debug)]
+pub struct FundingReclaimed {
+    /// The number of lamports reclaimed.
+    pub amount: u64,
+    /// The address the lamports were sent to.
+    pub pay_to: Pubkey,
+    /// The address of the queue.
+    pub queue: Pubkey,
+    /// The sponsor who reclaimed.
+    pub sponsor: Pubkey,
+}
+
+/// Emitted when a queue is paused because its balance fell below its low-balance threshold.
+#[event]
+#[derive(Debug)]
+pub struct QueueBalanceLow {
+    /// The number of spendable lamports, above the rent-exempt minimum, the queue holds.
+    pub balance: u64,
+    /// The address of the queue.
+    pub queue: Pubkey,
+    /// The low-balance threshold of the queue.
+    pub threshold: u64,
+}
+
+/// Emitted when a queue is created.
+#[event]
+#[derive(Debug)]
+pub struct QueueCreated {
+    /// The owner of the queue.
+    pub authority: Pubkey,
+    /// The id of the queue, given by the authority.
+    pub id: String,
+    /// The address of the queue.
+    pub queue: Pubkey,
+    /// The triggering event to kickoff a thread.
+    pub trigger: Trigger,
+}
+
+/// Emitted when a queue is cranked.
+#[event]
+#[derive(Debug)]
+pub struct QueueCranked {
+    /// The number of lamports debited from the queue to pay the worker for this crank.
+    pub fees_paid: u64,
+    /// The address of the queue.
+    pub queue: Pubkey,
+    /// The slot the crank was processed in.
+    pub slot: u64,
+    /// Whether or not this crank completed the thread.
+    pub thread_complete: bool,
+    /// The context of the event which triggered the current thread.
+    pub trigger_context: TriggerContext,
+    /// The cluster timestamp the crank was processed at.
+    pub unix_timestamp: i64,
+    /// The worker who submitted the crank.
+    pub worker: Pubkey,
+}
+
+/// Emitted when a queue is deleted.
+#[event]
+#[derive(Debug)]
+pub struct QueueDeleted {
+    /// The address the queue's lamports were returned to.
+    pub close_to: Pubkey,
+    /// The address of the queue.
+    pub queue: Pubkey,
+}
+
+/// Emitted when a worker reports a failed crank.
+#[event]
+#[derive(Debug)]
+pub struct QueueFailed {
+    /// The number of cranks that have failed since the last successful crank.
+    pub consecutive_failures: u64,
+    /// Whether or not the queue was paused because it exhausted its retries.
+    pub paused: bool,
+    /// The address of the queue.
+    pub queue: Pubkey,
+    /// The slot at which the queue may be retried.
+    pub retry_at: Option<u64>,
+    /// The worker who reported the failure.
+    pub worker: Pubkey,
+}
+
+/// Emitted when a sponsor funds a queue.
+#[event]
+#[derive(Debug)]
+pub struct QueueFunded {
+    /// The number of lamports contributed.
+    pub amount: u64,
+    /// The address of the queue.
+    pub queue: Pubkey,
+    /// The sponsor who funded the queue.
+    pub sponsor: Pubkey,
+}
+
+/// Emitted when a queue is paused.
+#[event]
+#[derive(Debug)]
+pub struct QueuePaused {
+    /// The address of the queue.
+    pub queue: Pubkey,
+}
+
+/// Emitted when a queue is resumed.
+#[event]
+#[derive(Debug)]
+pub struct QueueResumed {
+    /// The address of the queue.
+    pub queue: Pubkey,
+}
+
+/// Emitted when tokens are withdrawn from a queue's token account.
+#[event]
+#[derive(Debug)]
+pub struct QueueTokensWithdrawn {
+    /// The number of tokens withdrawn, in the mint's base units.
+    pub amount: u64,
+    /// The mint of the tokens withdrawn.
+    pub mint: Pubkey,
+    /// The token account the tokens were sent to.
+    pub pay_to: Pubkey,
+    /// The address of the queue.
+    pub queue: Pubkey,
+}
+
+/// Emitted when a queue's properties are updated.
+#[event]
+#[derive(Debug)]
+pub struct QueueUpdated {
+    /// The address of the queue.
+    pub queue: Pubkey,
+    /// The maximum number of cranks allowed per slot.
+    pub rate_limit: u64,
+    /// The triggering event to kickoff a thread.
+    pub trigger: Trigger,
+}
+
+/// Emitted when lamports are withdrawn from a queue.
+#[event]
+#[derive(Debug)]
+pub struct QueueWithdrawn {
+    /// The number of lamports withdrawn.
+    pub amount: u64,
+    /// The address the lamports were sent to.
+    pub pay_to: Pubkey,
+    /// The address of the queue.
+    pub queue: Pubkey,
+}
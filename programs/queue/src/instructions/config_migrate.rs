@@ -0,0 +1,66 @@
+use {
+    crate::objects::*,
+    anchor_lang::{
+        prelude::*,
+        solana_program::system_program,
+        system_program::{transfer, Transfer},
+    },
+    std::mem::size_of,
+};
+
+/// Accounts required by the `config_migrate` instruction.
+#[derive(Accounts)]
+pub struct ConfigMigrate<'info> {
+    /// The program admin.
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// The program config account, which may have been initialized before its latest fields were appended.
+    /// CHECK: This account is deserialized once it has been reallocated to the current config size.
+    #[account(
+        mut,
+        seeds = [SEED_CONFIG],
+        bump,
+        owner = crate::ID
+    )]
+    pub config: UncheckedAccount<'info>,
+
+    /// The Solana system program.
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<ConfigMigrate>) -> Result<()> {
+    // Get accounts
+    let admin = &ctx.accounts.admin;
+    let config = &ctx.accounts.config;
+    let system_program = &ctx.accounts.system_program;
+
+    // Reallocate mem for the config account, zeroing the appended fields
+    let data_len = 8 + size_of::<Config>();
+    if config.data_len() < data_len {
+        config.to_account_info().realloc(data_len, true)?;
+    }
+
+    // If lamports are required to maintain rent-exemption, pay them
+    let minimum_rent = Rent::get().unwrap().minimum_balance(data_len);
+    if minimum_rent > config.lamports() {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: admin.to_account_info(),
+                    to: config.to_account_info(),
+                },
+            ),
+            minimum_rent.checked_sub(config.lamports()).unwrap(),
+        )?;
+    }
+
+    // Verify the signer is the admin, and set defaults for the appended fields
+    let config_info = config.to_account_info();
+    let mut config = Account::<Config>::try_from(&config_info)?;
+    require_keys_eq!(config.admin, admin.key(), ErrorCode::ConstraintHasOne);
+    config.migrate()?;
+    config.exit(&crate::ID)
+}
@@ -1,4 +1,11 @@
-use {crate::objects::*, anchor_lang::prelude::*};
+use {
+    crate::{errors::*, objects::*},
+    anchor_lang::prelude::*,
+    anchor_spl::{
+        associated_token::get_associated_token_address,
+        token::{self, TokenAccount},
+    },
+};
 
 /// Accounts required by the `config_update` instruction.
 #[derive(Accounts)]
@@ -19,6 +26,25 @@ pub struct ConfigUpdate<'info> {
 }
 
 pub fn handler(ctx: Context<ConfigUpdate>, settings: ConfigSettings) -> Result<()> {
+    // Get accounts
     let config = &mut ctx.accounts.config;
+
+    // If a mint is whitelisted, require the config's token account for it to exist, so that token crank fees can be
+    // withheld from workers outside the pool. The token account is read from the remaining accounts.
+    if let Some(fee_mint) = settings.fee_mint {
+        let config_tokens = ctx
+            .remaining_accounts
+            .first()
+            .ok_or(ClockworkError::InvalidTokenAccount)?;
+        require!(
+            config_tokens
+                .key()
+                .eq(&get_associated_token_address(&config.key(), &fee_mint.mint))
+                && config_tokens.owner.eq(&token::ID),
+            ClockworkError::InvalidTokenAccount
+        );
+        TokenAccount::try_deserialize(&mut config_tokens.try_borrow_data()?.as_ref())?;
+    }
+
     config.update(settings)
 }
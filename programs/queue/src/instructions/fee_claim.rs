@@ -0,0 +1,37 @@
+use {crate::objects::*, anchor_lang::prelude::*};
+
+/// Accounts required by the `fee_claim` instruction.
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct FeeClaim<'info> {
+    /// The worker's fee account.
+    #[account(
+        mut,
+        seeds = [
+            SEED_FEE,
+            fee.worker.as_ref(),
+        ],
+        bump,
+        has_one = worker,
+    )]
+    pub fee: Account<'info, Fee>,
+
+    /// The account to withdraw lamports to.
+    #[account(mut)]
+    pub pay_to: SystemAccount<'info>,
+
+    /// The worker who owns the fee account.
+    #[account()]
+    pub worker: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FeeClaim>, amount: u64) -> Result<()> {
+    // Get accounts
+    let fee = &mut ctx.accounts.fee;
+    let pay_to = &mut ctx.accounts.pay_to;
+
+    // Claim the worker's balance
+    fee.claim_balance(amount, pay_to)?;
+
+    Ok(())
+}
@@ -0,0 +1,70 @@
+use {
+    crate::objects::*,
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+
+/// Accounts required by the `fee_token_claim` instruction.
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct FeeTokenClaim<'info> {
+    /// The worker's fee account.
+    #[account(
+        seeds = [
+            SEED_FEE,
+            worker.key().as_ref(),
+        ],
+        bump,
+    )]
+    pub fee: Account<'info, Fee>,
+
+    /// The fee account's token account, holding the tokens escrowed to the worker.
+    #[account(
+        mut,
+        associated_token::authority = fee,
+        associated_token::mint = mint,
+    )]
+    pub fee_tokens: Account<'info, TokenAccount>,
+
+    /// The mint of the escrowed tokens.
+    #[account()]
+    pub mint: Account<'info, Mint>,
+
+    /// The token account to withdraw tokens to.
+    #[account(mut, token::mint = mint)]
+    pub pay_to: Account<'info, TokenAccount>,
+
+    /// The SPL token program.
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Program<'info, Token>,
+
+    /// The worker who owns the fee account.
+    #[account()]
+    pub worker: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FeeTokenClaim>, amount: u64) -> Result<()> {
+    // Get accounts
+    let fee = &ctx.accounts.fee;
+    let fee_tokens = &ctx.accounts.fee_tokens;
+    let pay_to = &ctx.accounts.pay_to;
+    let token_program = &ctx.accounts.token_program;
+    let worker = &ctx.accounts.worker;
+
+    // Transfer tokens from the fee account's token account to the pay_to account
+    let bump = *ctx.bumps.get("fee").unwrap();
+    transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: fee_tokens.to_account_info(),
+                to: pay_to.to_account_info(),
+                authority: fee.to_account_info(),
+            },
+            &[&[SEED_FEE, worker.key().as_ref(), &[bump]]],
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
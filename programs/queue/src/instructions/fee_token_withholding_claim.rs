@@ -0,0 +1,67 @@
+use {
+    crate::objects::*,
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+
+/// Accounts required by the `fee_token_withholding_claim` instruction.
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct FeeTokenWithholdingClaim<'info> {
+    /// The program admin.
+    #[account()]
+    pub admin: Signer<'info>,
+
+    /// The program config account.
+    #[account(
+        seeds = [SEED_CONFIG],
+        bump,
+        has_one = admin,
+    )]
+    pub config: Account<'info, Config>,
+
+    /// The config account's token account, holding the tokens withheld from workers.
+    #[account(
+        mut,
+        associated_token::authority = config,
+        associated_token::mint = mint,
+    )]
+    pub config_tokens: Account<'info, TokenAccount>,
+
+    /// The mint of the withheld tokens.
+    #[account()]
+    pub mint: Account<'info, Mint>,
+
+    /// The token account to withdraw tokens to.
+    #[account(mut, token::mint = mint)]
+    pub pay_to: Account<'info, TokenAccount>,
+
+    /// The SPL token program.
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<FeeTokenWithholdingClaim>, amount: u64) -> Result<()> {
+    // Get accounts
+    let config = &ctx.accounts.config;
+    let config_tokens = &ctx.accounts.config_tokens;
+    let pay_to = &ctx.accounts.pay_to;
+    let token_program = &ctx.accounts.token_program;
+
+    // Transfer the tokens withheld from workers to the pay_to account
+    let bump = *ctx.bumps.get("config").unwrap();
+    transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: config_tokens.to_account_info(),
+                to: pay_to.to_account_info(),
+                authority: config.to_account_info(),
+            },
+            &[&[SEED_CONFIG, &[bump]]],
+        ),
+        amount,
+    )?;
+
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use {crate::objects::*, anchor_lang::prelude::*};
+
+/// Accounts required by the `fee_withholding_claim` instruction.
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct FeeWithholdingClaim<'info> {
+    /// The program admin.
+    #[account()]
+    pub admin: Signer<'info>,
+
+    /// The program config account.
+    #[account(address = Config::pubkey(), has_one = admin)]
+    pub config: Account<'info, Config>,
+
+    /// The worker's fee account.
+    #[account(
+        mut,
+        seeds = [
+            SEED_FEE,
+            fee.worker.as_ref(),
+        ],
+        bump,
+    )]
+    pub fee: Account<'info, Fee>,
+
+    /// The account to withdraw lamports to.
+    #[account(mut)]
+    pub pay_to: SystemAccount<'info>,
+}
+
+pub fn handler(ctx: Context<FeeWithholdingClaim>, amount: u64) -> Result<()> {
+    // Get accounts
+    let fee = &mut ctx.accounts.fee;
+    let pay_to = &mut ctx.accounts.pay_to;
+
+    // Claim the lamports withheld from the worker
+    fee.claim_withholding(amount, pay_to)?;
+
+    Ok(())
+}
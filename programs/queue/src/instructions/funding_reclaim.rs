@@ -0,0 +1,56 @@
+use {
+    crate::{events::FundingReclaimed, objects::*},
+    anchor_lang::{prelude::*, AccountsClose},
+};
+
+/// Accounts required by the `funding_reclaim` instruction.
+#[derive(Accounts)]
+pub struct FundingReclaim<'info> {
+    /// The funding account of the deleted queue.
+    #[account(
+        mut,
+        seeds = [
+            SEED_FUNDING,
+            funding.queue.as_ref(),
+            funding.queue_created_at.to_le_bytes().as_ref(),
+        ],
+        bump,
+    )]
+    pub funding: Account<'info, Funding>,
+
+    /// The account to return the sponsor's share to. Once every sponsor has reclaimed, the funding account's rent
+    /// is returned here too.
+    #[account(mut)]
+    pub pay_to: SystemAccount<'info>,
+
+    /// The sponsor reclaiming their share.
+    #[account()]
+    pub sponsor: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<FundingReclaim>) -> Result<()> {
+    // Get accounts
+    let funding = &mut ctx.accounts.funding;
+    let pay_to = &mut ctx.accounts.pay_to;
+    let sponsor = &ctx.accounts.sponsor;
+
+    // Pay the sponsor's share of the reclaimable lamports
+    let amount = funding.reclaim(&pay_to.to_account_info(), sponsor.key())?;
+
+    // Emit an event
+    emit!(FundingReclaimed {
+        amount,
+        pay_to: pay_to.key(),
+        queue: funding.queue,
+        sponsor: sponsor.key(),
+    });
+
+    // If every sponsor has reclaimed their share, close the funding account
+    if funding.sponsors.is_empty() {
+        funding.close(pay_to.to_account_info())?;
+    } else {
+        funding.realloc()?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use {crate::objects::*, anchor_lang::prelude::*};
+
+/// Accounts required by the `instruction_buffer_close` instruction.
+#[derive(Accounts)]
+pub struct InstructionBufferClose<'info> {
+    /// The authority (owner) of the instruction buffer.
+    #[account()]
+    pub authority: Signer<'info>,
+
+    /// The address to return the data rent lamports to.
+    #[account(mut)]
+    pub close_to: SystemAccount<'info>,
+
+    /// The instruction buffer to be closed.
+    #[account(
+        mut,
+        seeds = [
+            SEED_INSTRUCTION_BUFFER,
+            instruction_buffer.authority.as_ref(),
+            instruction_buffer.id.as_bytes(),
+        ],
+        bump,
+        has_one = authority,
+        close = close_to
+    )]
+    pub instruction_buffer: Account<'info, InstructionBuffer>,
+}
+
+pub fn handler(_ctx: Context<InstructionBufferClose>) -> Result<()> {
+    Ok(())
+}
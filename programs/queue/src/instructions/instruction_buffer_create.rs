@@ -0,0 +1,52 @@
+use {
+    crate::{errors::ClockworkError, objects::*},
+    anchor_lang::{
+        prelude::*,
+        solana_program::{entrypoint::MAX_PERMITTED_DATA_INCREASE, system_program},
+    },
+};
+
+/// Accounts required by the `instruction_buffer_create` instruction.
+#[derive(Accounts)]
+#[instruction(id: String, size: u64)]
+pub struct InstructionBufferCreate<'info> {
+    /// The authority (owner) of the instruction buffer. Buffers are created by cross-program invocation, which
+    /// limits the size of the new account, so this is checked before the buffer is initialized.
+    #[account(
+        constraint = InstructionBuffer::space(&id, size) <= MAX_PERMITTED_DATA_INCREASE @ ClockworkError::InstructionBufferTooLarge
+    )]
+    pub authority: Signer<'info>,
+
+    /// The instruction buffer to be created.
+    #[account(
+        init,
+        seeds = [
+            SEED_INSTRUCTION_BUFFER,
+            authority.key().as_ref(),
+            id.as_bytes(),
+        ],
+        bump,
+        payer = payer,
+        space = InstructionBuffer::space(&id, size),
+    )]
+    pub instruction_buffer: Account<'info, InstructionBuffer>,
+
+    /// The payer for account initializations.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The Solana system program.
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InstructionBufferCreate>, id: String, size: u64) -> Result<()> {
+    // Get accounts
+    let authority = &ctx.accounts.authority;
+    let instruction_buffer = &mut ctx.accounts.instruction_buffer;
+
+    // Initialize the instruction buffer
+    instruction_buffer.init(authority.key(), id, size)?;
+
+    Ok(())
+}
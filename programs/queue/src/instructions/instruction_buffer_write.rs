@@ -0,0 +1,33 @@
+use {crate::objects::*, anchor_lang::prelude::*};
+
+/// Accounts required by the `instruction_buffer_write` instruction.
+#[derive(Accounts)]
+#[instruction(data: Vec<u8>, offset: u64)]
+pub struct InstructionBufferWrite<'info> {
+    /// The authority (owner) of the instruction buffer.
+    #[account()]
+    pub authority: Signer<'info>,
+
+    /// The instruction buffer to write to.
+    #[account(
+        mut,
+        seeds = [
+            SEED_INSTRUCTION_BUFFER,
+            instruction_buffer.authority.as_ref(),
+            instruction_buffer.id.as_bytes(),
+        ],
+        bump,
+        has_one = authority,
+    )]
+    pub instruction_buffer: Account<'info, InstructionBuffer>,
+}
+
+pub fn handler(ctx: Context<InstructionBufferWrite>, data: Vec<u8>, offset: u64) -> Result<()> {
+    // Get accounts
+    let instruction_buffer = &mut ctx.accounts.instruction_buffer;
+
+    // Write the chunk into the buffer
+    instruction_buffer.write(data, offset)?;
+
+    Ok(())
+}
@@ -1,19 +1,45 @@
+pub mod config_migrate;
 pub mod config_update;
+pub mod fee_claim;
+pub mod fee_token_claim;
+pub mod fee_token_withholding_claim;
+pub mod fee_withholding_claim;
+pub mod funding_reclaim;
 pub mod initialize;
+pub mod instruction_buffer_close;
+pub mod instruction_buffer_create;
+pub mod instruction_buffer_write;
 pub mod queue_crank;
 pub mod queue_create;
 pub mod queue_delete;
+pub mod queue_fail;
+pub mod queue_fund;
+pub mod queue_migrate;
 pub mod queue_pause;
 pub mod queue_resume;
+pub mod queue_token_withdraw;
 pub mod queue_update;
 pub mod queue_withdraw;
 
+pub use config_migrate::*;
 pub use config_update::*;
+pub use fee_claim::*;
+pub use fee_token_claim::*;
+pub use fee_token_withholding_claim::*;
+pub use fee_withholding_claim::*;
+pub use funding_reclaim::*;
 pub use initialize::*;
+pub use instruction_buffer_close::*;
+pub use instruction_buffer_create::*;
+pub use instruction_buffer_write::*;
 pub use queue_crank::*;
 pub use queue_create::*;
 pub use queue_delete::*;
+pub use queue_fail::*;
+pub use queue_fund::*;
+pub use queue_migrate::*;
 pub use queue_pause::*;
 pub use queue_resume::*;
+pub use queue_token_withdraw::*;
 pub use queue_update::*;
 pub use queue_withdraw::*;
@@ -1,23 +1,29 @@
 use {
-    crate::{errors::*, objects::*},
-    anchor_lang::{prelude::*, system_program},
-    chrono::{DateTime, NaiveDateTime, Utc},
-    clockwork_cron::Schedule,
-    clockwork_pool_program::objects::Pool,
-    std::{
-        collections::hash_map::DefaultHasher,
-        hash::{Hash, Hasher},
-        mem::size_of,
-        str::FromStr,
+    crate::{
+        errors::*,
+        events::{QueueBalanceLow, QueueCranked, QueueDeleted},
+        objects::*,
+        utils::{anchor_sighash, COMPUTE_BUDGET_PROGRAM_ID},
+    },
+    anchor_lang::{
+        prelude::*,
+        solana_program::sysvar::{self, instructions::load_instruction_at_checked},
+        system_program,
     },
+    anchor_spl::{
+        associated_token::{self, get_associated_token_address, Create},
+        token::{self, transfer, Transfer},
+    },
+    clockwork_pool_program::objects::Pool,
+    std::mem::size_of,
 };
 
-/// Number of lamports to reimburse the worker with after they've submitted a transaction's worth of cranks.
-const TRANSACTION_BASE_FEE_REIMBURSEMENT: u64 = 5000;
+/// The index of the worker among the accounts of a `queue_crank` instruction.
+const CRANK_WORKER_ACCOUNT_INDEX: usize = 6;
 
 /// Accounts required by the `queue_crank` instruction.
 #[derive(Accounts)]
-#[instruction(data_hash: Option<u64>)]
+#[instruction(data_hash: Option<u64>, lamports_per_signature: u64)]
 pub struct QueueCrank<'info> {
     /// The program config account.
     #[account(address = Config::pubkey())]
@@ -36,8 +42,13 @@ pub struct QueueCrank<'info> {
     )]
     pub fee: Box<Account<'info, Fee>>,
 
-    /// The active worker pool.
-    #[account(address = config.worker_pool)]
+    /// The instructions sysvar, for reading the fee structure of the current transaction.
+    /// CHECK: This account is verified against the instructions sysvar address.
+    #[account(address = sysvar::instructions::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    /// The worker pool allowed to crank the queue.
+    #[account(address = queue.pool.unwrap_or(config.worker_pool))]
     pub pool: Box<Account<'info, Pool>>,
 
     /// The queue to crank.
@@ -62,132 +73,88 @@ pub struct QueueCrank<'info> {
     pub worker: Signer<'info>,
 }
 
-pub fn handler(ctx: Context<QueueCrank>, data_hash: Option<u64>) -> Result<()> {
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, QueueCrank<'info>>,
+    data_hash: Option<u64>,
+    lamports_per_signature: u64,
+) -> Result<()> {
     // Get accounts
     let config = &ctx.accounts.config;
     let fee = &mut ctx.accounts.fee;
+    let instructions = &ctx.accounts.instructions;
     let pool = &ctx.accounts.pool;
     let queue = &mut ctx.accounts.queue;
+    let system_program = &ctx.accounts.system_program;
     let worker = &ctx.accounts.worker;
 
-    // If this queue does not have a next_instruction, verify the queue's trigger has been met and a new exec_context can be created.
-    let current_slot = Clock::get().unwrap().slot;
-    if queue.next_instruction.is_none() {
-        match queue.trigger.clone() {
-            Trigger::Account { pubkey } => {
-                // Require the provided data hash is non-null.
-                let data_hash = match data_hash {
-                    None => return Err(ClockworkError::InvalidQueueState.into()),
-                    Some(data_hash) => data_hash,
-                };
-
-                // Verify proof that account data has been updated.
-                match ctx.remaining_accounts.first() {
-                    None => {}
-                    Some(account_info) => {
-                        // Verify the remaining account is the account this queue is listening for.
-                        require!(pubkey.eq(account_info.key), ClockworkError::InvalidTrigger);
-
-                        // Begin computing the data hash of this account.
-                        let mut hasher = DefaultHasher::new();
-                        let data = &account_info.try_borrow_data().unwrap();
-                        data.to_vec().hash(&mut hasher);
-
-                        // Check the exec context for the prior data hash.
-                        let expected_data_hash = match queue.exec_context.clone() {
-                            None => {
-                                // This queue has not begun executing yet.
-                                // There is no prior data hash to include in our hash.
-                                hasher.finish()
-                            }
-                            Some(exec_context) => {
-                                match exec_context.trigger_context {
-                                    TriggerContext::Account {
-                                        data_hash: prior_data_hash,
-                                    } => {
-                                        // Inject the prior data hash as a seed.
-                                        prior_data_hash.hash(&mut hasher);
-                                        hasher.finish()
-                                    }
-                                    _ => return Err(ClockworkError::InvalidQueueState.into()),
-                                }
-                            }
-                        };
-
-                        // Verify the data hash provided by the worker is equal to the expected data hash.
-                        // This proves the account has been updated since the last crank and the worker has seen the new data.
-                        require!(
-                            data_hash.eq(&expected_data_hash),
-                            ClockworkError::InvalidTrigger
-                        );
-
-                        // Set a new exec context with the new data hash and slot number.
-                        queue.exec_context = Some(ExecContext {
-                            cranks_since_reimbursement: 0,
-                            cranks_since_slot: 0,
-                            last_crank_at: current_slot,
-                            trigger_context: TriggerContext::Account { data_hash },
-                        })
-                    }
-                }
-            }
-            Trigger::Cron {
-                schedule,
-                skippable,
-            } => {
-                // Get the reference timestamp for calculating the queue's scheduled target timestamp.
-                let reference_timestamp = match queue.exec_context.clone() {
-                    None => queue.created_at.unix_timestamp,
-                    Some(exec_context) => match exec_context.trigger_context {
-                        TriggerContext::Cron { started_at } => started_at,
-                        _ => return Err(ClockworkError::InvalidQueueState.into()),
-                    },
-                };
-
-                // Verify the current timestamp is greater than or equal to the threshold timestamp.
-                let current_timestamp = Clock::get().unwrap().unix_timestamp;
-                let threshold_timestamp = next_timestamp(reference_timestamp, schedule.clone())
-                    .ok_or(ClockworkError::InvalidTrigger)?;
-                require!(
-                    current_timestamp >= threshold_timestamp,
-                    ClockworkError::InvalidTrigger
-                );
+    // If the queue names its own worker pool, only workers in that pool may crank it.
+    let is_authorized_worker = pool.workers.contains(&worker.key());
+    require!(
+        queue.pool.is_none() || is_authorized_worker,
+        ClockworkError::UnauthorizedWorker
+    );
 
-                // If the schedule is marked as skippable, set the started_at of the exec context
-                // to be the threshold moment just before the current timestamp.
-                let started_at = if skippable && current_timestamp > threshold_timestamp {
-                    prev_timestamp(current_timestamp, schedule)
-                        .ok_or(ClockworkError::InvalidTrigger)?
-                } else {
-                    threshold_timestamp
-                };
-
-                // Set the exec context.
-                queue.exec_context = Some(ExecContext {
-                    cranks_since_reimbursement: 0,
-                    cranks_since_slot: 0,
-                    last_crank_at: current_slot,
-                    trigger_context: TriggerContext::Cron { started_at },
-                });
-            }
-            Trigger::Immediate => {
-                // Set the exec context.
-                require!(
-                    queue.exec_context.is_none(),
-                    ClockworkError::InvalidQueueState
-                );
-                queue.exec_context = Some(ExecContext {
-                    cranks_since_reimbursement: 0,
-                    cranks_since_slot: 0,
-                    last_crank_at: current_slot,
-                    trigger_context: TriggerContext::Immediate,
-                });
-            }
+    // Get this crank's share of the transaction fee to reimburse the worker with. The signature fee is reported by
+    // the worker, since the runtime no longer exposes it to programs, and is bounded by the config.
+    let transaction_fee_reimbursement = transaction_fee_share(
+        instructions,
+        lamports_per_signature.min(config.max_lamports_per_signature),
+        queue,
+    )?;
+
+    // If this queue has expired and does not have any next_instructions, refuse to start a new exec_context.
+    // If auto-close is enabled, reimburse the worker and close the queue to its authority instead.
+    // The authority is only needed to auto-close the queue, so it is read from the remaining accounts.
+    let bump = ctx.bumps.get("queue").unwrap();
+    let current_slot = Clock::get().unwrap().slot;
+    let is_expired = queue.is_expired(Clock::get().unwrap().unix_timestamp);
+    if queue.next_instructions.is_empty() && is_expired {
+        require!(queue.auto_close, ClockworkError::QueueExpired);
+        let reimbursement = transaction_fee_reimbursement.min(queue.spendable_balance()?);
+        if is_authorized_worker {
+            fee.escrow_balance(reimbursement, queue)?;
+        } else {
+            fee.escrow_withholding(reimbursement, queue)?;
         }
+        let authority = queue.authority;
+        return close_queue_to(ctx.remaining_accounts, *bump, authority, queue);
+    }
+
+    // Record the worker's lamports before invoking any of the queue's instructions, including its predicate.
+    let worker_lamports_pre = worker.lamports();
+
+    // If this queue does not have any next_instructions, verify the queue's trigger has been met and a new exec_context can be created.
+    if queue.next_instructions.is_empty() {
+        // Verify the execution rate limit has not been reached, and count this execution against it.
+        let (window_exec_count, window_started_at) =
+            queue.count_exec_in_window(&Clock::get().unwrap())?;
+
+        // Verify the trigger has fired, and start a new exec context with the triggering event.
+        let trigger_context = queue.verify_trigger(ctx.remaining_accounts, data_hash, worker)?;
+        queue.exec_context = Some(ExecContext {
+            cranks_since_slot: 0,
+            last_crank_at: current_slot,
+            trigger_context: Some(trigger_context),
+            window_exec_count,
+            window_started_at,
+        });
+
+        // Increment the execution count
+        queue.exec_count = queue.exec_count.checked_add(1).unwrap();
+
+        // Start a new record in the execution history
+        queue.push_exec_record(ExecRecord {
+            crank_count: 0,
+            ended_at: current_slot,
+            fees_charged: 0,
+            started_at: current_slot,
+            trigger_context,
+            worker: worker.key(),
+        });
     }
 
     // If the rate limit has been met, exit early.
-    match queue.exec_context {
+    let trigger_context = match queue.exec_context {
         None => return Err(ClockworkError::InvalidQueueState.into()),
         Some(exec_context) => {
             if exec_context.last_crank_at == Clock::get().unwrap().slot
@@ -195,59 +162,544 @@ pub fn handler(ctx: Context<QueueCrank>, data_hash: Option<u64>) -> Result<()> {
             {
                 return Err(ClockworkError::RateLimitExeceeded.into());
             }
+            exec_context
+                .trigger_context
+                .ok_or(ClockworkError::InvalidQueueState)?
         }
-    }
+    };
+
+    // Record the queue's lamports before the crank
+    let queue_lamports_pre = queue.to_account_info().lamports();
 
     // Crank the queue
-    let bump = ctx.bumps.get("queue").unwrap();
-    queue.crank(ctx.remaining_accounts, *bump, worker)?;
+    let close_to = queue.crank(ctx.remaining_accounts, *bump, worker, worker_lamports_pre)?;
 
     // If worker is in the pool, pay automation fees.
-    let is_authorized_worker = pool.clone().into_inner().workers.contains(&worker.key());
-    if is_authorized_worker {
-        fee.escrow_balance(config.crank_fee, queue)?;
-    } else {
-        fee.escrow_withholding(config.crank_fee, queue)?;
+    // Queues paying in a whitelisted mint pay the config's token crank fee from their token account instead.
+    let fee_mint = config
+        .fee_mint
+        .filter(|fee_mint| queue.fee_mint.eq(&Some(fee_mint.mint)));
+    match fee_mint {
+        None => {
+            let crank_fee = queue.crank_fee(config.crank_fee);
+            if is_authorized_worker {
+                fee.escrow_balance(crank_fee, queue)?;
+            } else {
+                fee.escrow_withholding(crank_fee, queue)?;
+            }
+        }
+        Some(fee_mint) => {
+            let escrow = if is_authorized_worker {
+                // Create the worker's fee token account if this is its first token crank fee in the mint.
+                create_fee_token_account(
+                    ctx.remaining_accounts,
+                    fee,
+                    &fee_mint,
+                    queue,
+                    system_program,
+                    worker,
+                )?;
+                fee.key()
+            } else {
+                config.key()
+            };
+            escrow_crank_fee_tokens(ctx.remaining_accounts, *bump, escrow, fee_mint, queue)?;
+        }
     }
 
-    // If the queue has no more work or the number of cranks since the last payout has reached the rate limit,
-    // reimburse the worker for the transaction base fee.
-    match queue.exec_context {
-        None => return Err(ClockworkError::InvalidQueueState.into()),
-        Some(exec_context) => {
-            if queue.next_instruction.is_none()
-                || exec_context.cranks_since_reimbursement >= queue.rate_limit
+    // Reimburse the worker for this crank's share of the transaction fee.
+    fee.escrow_balance(transaction_fee_reimbursement, queue)?;
+
+    // Update the latest record in the execution history
+    let fees_paid = queue_lamports_pre.saturating_sub(queue.to_account_info().lamports());
+    if let Some(exec_record) = queue.exec_history.last_mut() {
+        exec_record.crank_count = exec_record.crank_count.checked_add(1).unwrap();
+        exec_record.ended_at = current_slot;
+        exec_record.fees_charged = exec_record.fees_charged.checked_add(fees_paid).unwrap();
+        exec_record.worker = worker.key();
+    }
+
+    // If the queue's balance has fallen below its low-balance threshold, pause it.
+    if let Some(low_balance_threshold) = queue.low_balance_threshold {
+        let balance = queue.spendable_balance()?;
+        if balance < low_balance_threshold && !queue.paused {
+            queue.paused = true;
+            emit!(QueueBalanceLow {
+                balance,
+                queue: queue.key(),
+                threshold: low_balance_threshold,
+            });
+        }
+    }
+
+    // Emit an event
+    let clock = Clock::get().unwrap();
+    emit!(QueueCranked {
+        fees_paid,
+        queue: queue.key(),
+        slot: clock.slot,
+        thread_complete: queue.next_instructions.is_empty(),
+        trigger_context,
+        unix_timestamp: clock.unix_timestamp,
+        worker: worker.key(),
+    });
+
+    // If a target program requested it, close the queue.
+    if let Some(close_to) = close_to {
+        return close_queue_to(ctx.remaining_accounts, *bump, close_to, queue);
+    }
+
+    // If the thread is complete and the queue has now expired, auto-close it to its authority.
+    if queue.auto_close
+        && queue.next_instructions.is_empty()
+        && queue.is_expired(clock.unix_timestamp)
+    {
+        let authority = queue.authority;
+        return close_queue_to(ctx.remaining_accounts, *bump, authority, queue);
+    }
+
+    Ok(())
+}
+
+/// Close the queue to the given address, which must be one of the remaining accounts, and emit an event.
+fn close_queue_to<'info>(
+    account_infos: &[AccountInfo<'info>],
+    bump: u8,
+    close_to: Pubkey,
+    queue: &mut Account<'info, Queue>,
+) -> Result<()> {
+    let close_to_account_info = account_infos
+        .iter()
+        .find(|account_info| close_to.eq(account_info.key))
+        .cloned()
+        .ok_or(ClockworkError::InvalidCloseToAccount)?;
+    close_queue(account_infos, bump, close_to_account_info, queue)?;
+    emit!(QueueDeleted {
+        close_to,
+        queue: queue.key(),
+    });
+    Ok(())
+}
+
+/// Create the worker's fee token account for the mint, if this is the first token crank fee escrowed to it. The worker
+/// pays the account's rent and is reimbursed from the queue through its fee account. The token account, the mint, the
+/// associated token program, the token program, and the rent sysvar are read from the remaining accounts.
+fn create_fee_token_account<'info>(
+    account_infos: &[AccountInfo<'info>],
+    fee: &mut Account<'info, Fee>,
+    fee_mint: &FeeMint,
+    queue: &mut Account<'info, Queue>,
+    system_program: &Program<'info, System>,
+    worker: &Signer<'info>,
+) -> Result<()> {
+    let find_account_info = |pubkey: Pubkey| {
+        account_infos
+            .iter()
+            .find(|account_info| pubkey.eq(account_info.key))
+            .cloned()
+            .ok_or(ClockworkError::InvalidTokenAccount)
+    };
+
+    // Exit early if the token account already exists.
+    let fee_tokens = find_account_info(get_associated_token_address(&fee.key(), &fee_mint.mint))?;
+    if fee_tokens.owner.eq(&token::ID) {
+        return Ok(());
+    }
+
+    // Create the token account, and reimburse the worker for its rent.
+    let worker_lamports_pre = worker.lamports();
+    associated_token::create(CpiContext::new(
+        find_account_info(associated_token::ID)?,
+        Create {
+            payer: worker.to_account_info(),
+            associated_token: fee_tokens,
+            authority: fee.to_account_info(),
+            mint: find_account_info(fee_mint.mint)?,
+            system_program: system_program.to_account_info(),
+            token_program: find_account_info(token::ID)?,
+            rent: find_account_info(sysvar::rent::ID)?,
+        },
+    ))?;
+    let rent = worker_lamports_pre.saturating_sub(worker.lamports());
+    fee.escrow_balance(rent, queue)
+}
+
+/// Transfer the token crank fee from the queue's token account to the token account of the given escrow: the
+/// worker's fee account, or the config account if the fee is withheld. The token accounts and the token program are
+/// read from the remaining accounts.
+fn escrow_crank_fee_tokens<'info>(
+    account_infos: &[AccountInfo<'info>],
+    bump: u8,
+    escrow: Pubkey,
+    fee_mint: FeeMint,
+    queue: &Account<'info, Queue>,
+) -> Result<()> {
+    let find_account_info = |pubkey: Pubkey| {
+        account_infos
+            .iter()
+            .find(|account_info| pubkey.eq(account_info.key))
+            .cloned()
+            .ok_or(ClockworkError::InvalidTokenAccount)
+    };
+    transfer(
+        CpiContext::new_with_signer(
+            find_account_info(token::ID)?,
+            Transfer {
+                from: find_account_info(get_associated_token_address(
+                    &queue.key(),
+                    &fee_mint.mint,
+                ))?,
+                to: find_account_info(get_associated_token_address(&escrow, &fee_mint.mint))?,
+                authority: queue.to_account_info(),
+            },
+            &[&[
+                SEED_QUEUE,
+                queue.authority.as_ref(),
+                queue.id.as_bytes(),
+                &[bump],
+            ]],
+        ),
+        fee_mint.crank_fee,
+    )
+}
+
+/// The share of the current transaction's fee owed by a single crank. The fee is made up of the signature fees and
+/// the priority fee, where the priority fee is capped by the queue's own compute unit price and limit. It is split
+/// evenly across all of the crank instructions in the transaction, rounding up. Only the signatures of the cranks'
+/// workers are charged, so the transaction may not include other instructions which require signatures.
+fn transaction_fee_share(
+    instructions: &AccountInfo,
+    lamports_per_signature: u64,
+    queue: &Queue,
+) -> Result<u64> {
+    // Scan the transaction's instructions for cranks, their workers, and compute budget settings.
+    let crank_sighash = anchor_sighash("queue_crank");
+    let mut workers: Vec<Pubkey> = vec![];
+    let mut crank_count: u64 = 0;
+    let mut compute_unit_limit: Option<u32> = None;
+    let mut compute_unit_price: Option<u64> = None;
+    let mut non_compute_budget_instruction_count: u32 = 0;
+    let mut index = 0;
+    while let Ok(instruction) = load_instruction_at_checked(index, instructions) {
+        if instruction.program_id.eq(&COMPUTE_BUDGET_PROGRAM_ID) {
+            match instruction.data.split_first() {
+                Some((2, data)) => {
+                    compute_unit_limit = data
+                        .get(..4)
+                        .and_then(|bytes| bytes.try_into().ok())
+                        .map(u32::from_le_bytes);
+                }
+                Some((3, data)) => {
+                    compute_unit_price = data
+                        .get(..8)
+                        .and_then(|bytes| bytes.try_into().ok())
+                        .map(u64::from_le_bytes);
+                }
+                _ => {}
+            }
+        } else {
+            if instruction.program_id.eq(&crate::ID) && instruction.data.starts_with(&crank_sighash)
             {
-                fee.escrow_balance(TRANSACTION_BASE_FEE_REIMBURSEMENT, queue)?;
-                queue.exec_context = Some(ExecContext {
-                    cranks_since_reimbursement: 0,
-                    ..exec_context
-                })
+                let worker = instruction
+                    .accounts
+                    .get(CRANK_WORKER_ACCOUNT_INDEX)
+                    .ok_or(ClockworkError::InvalidCrankTransaction)?;
+                if !workers.contains(&worker.pubkey) {
+                    workers.push(worker.pubkey);
+                }
+                crank_count = crank_count.checked_add(1).unwrap();
+            } else {
+                require!(
+                    instruction
+                        .accounts
+                        .iter()
+                        .all(|account| !account.is_signer),
+                    ClockworkError::InvalidCrankTransaction
+                );
             }
+            non_compute_budget_instruction_count =
+                non_compute_budget_instruction_count.saturating_add(1);
         }
+        index = index.checked_add(1).unwrap();
     }
 
-    Ok(())
+    // Cap the compute budget at the queue's own compute unit limit and price.
+    let compute_unit_limit = compute_unit_limit
+        .unwrap_or_else(|| default_compute_unit_limit(non_compute_budget_instruction_count))
+        .min(queue.compute_unit_limit.unwrap_or(MAX_COMPUTE_UNIT_LIMIT));
+    let compute_unit_price = compute_unit_price
+        .unwrap_or(0)
+        .min(queue.compute_unit_price.unwrap_or(0));
+
+    Ok(split_transaction_fee(
+        compute_unit_limit,
+        compute_unit_price,
+        crank_count,
+        lamports_per_signature,
+        workers.len() as u64,
+    ))
 }
 
-fn next_timestamp(after: i64, schedule: String) -> Option<i64> {
-    Schedule::from_str(&schedule)
-        .unwrap()
-        .next_after(&DateTime::<Utc>::from_utc(
-            NaiveDateTime::from_timestamp(after, 0),
-            Utc,
-        ))
-        .take()
-        .map(|datetime| datetime.timestamp())
+/// The compute unit limit the runtime grants a transaction which does not set one.
+fn default_compute_unit_limit(non_compute_budget_instruction_count: u32) -> u32 {
+    non_compute_budget_instruction_count
+        .saturating_mul(DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT)
+        .min(MAX_COMPUTE_UNIT_LIMIT)
+}
+
+/// Split a transaction's signature and priority fees evenly across its cranks, rounding up.
+fn split_transaction_fee(
+    compute_unit_limit: u32,
+    compute_unit_price: u64,
+    crank_count: u64,
+    lamports_per_signature: u64,
+    signer_count: u64,
+) -> u64 {
+    // Calculate the signature fees.
+    let signature_fee = signer_count.saturating_mul(lamports_per_signature);
+
+    // Calculate the priority fee, rounding up to the nearest lamport as the runtime does.
+    let priority_fee = (compute_unit_limit as u64)
+        .saturating_mul(compute_unit_price)
+        .saturating_add(999_999)
+        / 1_000_000;
+
+    // Split the fee across the cranks in the transaction.
+    let crank_count = crank_count.max(1);
+    signature_fee
+        .saturating_add(priority_fee)
+        .saturating_add(crank_count - 1)
+        / crank_count
 }
 
-fn prev_timestamp(before: i64, schedule: String) -> Option<i64> {
-    Schedule::from_str(&schedule)
-        .unwrap()
-        .prev_before(&DateTime::<Utc>::from_utc(
-            NaiveDateTime::from_timestamp(before, 0),
-            Utc,
-        ))
-        .take()
-        .map(|datetime| datetime.timestamp())
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        anchor_lang::solana_program::sysvar::instructions::{
+            construct_instructions_data, BorrowedAccountMeta, BorrowedInstruction,
+        },
+    };
+
+    fn queue(compute_unit_limit: Option<u32>, compute_unit_price: Option<u64>) -> Queue {
+        Queue {
+            address_lookup_tables: vec![],
+            authority: Pubkey::new_unique(),
+            auto_close: false,
+            completion_count: 0,
+            compute_unit_limit,
+            compute_unit_price,
+            consecutive_failures: 0,
+            created_at: ClockData::from(Clock::default()),
+            data_placeholders: vec![],
+            error_instruction: None,
+            exec_context: None,
+            exec_count: 0,
+            exec_history: vec![],
+            exec_history_capacity: MAX_EXEC_HISTORY_CAPACITY,
+            exec_rate_limit: None,
+            expires_at: None,
+            fee_bid: None,
+            fee_mint: None,
+            id: "test".into(),
+            kickoff_instructions: vec![],
+            last_failure_at: None,
+            low_balance_threshold: None,
+            max_executions: None,
+            max_retries: 3,
+            next_instructions: vec![],
+            paused: false,
+            pool: None,
+            rate_limit: 10,
+            sponsored_lamports: 0,
+            trigger: Trigger::Immediate,
+        }
+    }
+
+    fn set_compute_unit_limit_data(compute_unit_limit: u32) -> Vec<u8> {
+        [vec![2], compute_unit_limit.to_le_bytes().to_vec()].concat()
+    }
+
+    fn set_compute_unit_price_data(compute_unit_price: u64) -> Vec<u8> {
+        [vec![3], compute_unit_price.to_le_bytes().to_vec()].concat()
+    }
+
+    /// Calculate the transaction fee share of a transaction made of the given (program id, signers, data) instructions.
+    /// The first signer of a crank is placed at the worker's index of its accounts.
+    fn fee_share(instructions: &[(Pubkey, Vec<Pubkey>, Vec<u8>)], queue: &Queue) -> Result<u64> {
+        let crank_sighash = anchor_sighash("queue_crank");
+        let unsigned_pubkey = Pubkey::new_unique();
+        let borrowed_instructions = instructions
+            .iter()
+            .map(|(program_id, signers, data)| {
+                let mut accounts = vec![];
+                if data.starts_with(&crank_sighash) {
+                    for _ in 0..CRANK_WORKER_ACCOUNT_INDEX {
+                        accounts.push(BorrowedAccountMeta {
+                            pubkey: &unsigned_pubkey,
+                            is_signer: false,
+                            is_writable: false,
+                        });
+                    }
+                }
+                accounts.extend(signers.iter().map(|pubkey| BorrowedAccountMeta {
+                    pubkey,
+                    is_signer: true,
+                    is_writable: true,
+                }));
+                BorrowedInstruction {
+                    program_id,
+                    accounts,
+                    data,
+                }
+            })
+            .collect::<Vec<BorrowedInstruction>>();
+        let mut data = construct_instructions_data(&borrowed_instructions);
+        let mut lamports = 0;
+        let account_info = AccountInfo::new(
+            &sysvar::instructions::ID,
+            false,
+            false,
+            &mut lamports,
+            &mut data,
+            &sysvar::ID,
+            false,
+            0,
+        );
+        transaction_fee_share(&account_info, 5_000, queue)
+    }
+
+    #[test]
+    fn test_crank_worker_account_index() {
+        let worker = Pubkey::new_unique();
+        let account_metas = crate::accounts::QueueCrank {
+            config: Pubkey::new_unique(),
+            fee: Pubkey::new_unique(),
+            instructions: Pubkey::new_unique(),
+            pool: Pubkey::new_unique(),
+            queue: Pubkey::new_unique(),
+            system_program: Pubkey::new_unique(),
+            worker,
+        }
+        .to_account_metas(None);
+        assert_eq!(account_metas[CRANK_WORKER_ACCOUNT_INDEX].pubkey, worker);
+        assert!(account_metas[CRANK_WORKER_ACCOUNT_INDEX].is_signer);
+    }
+
+    #[test]
+    fn test_transaction_fee_share() {
+        let worker = Pubkey::new_unique();
+        let crank_data = anchor_sighash("queue_crank").to_vec();
+
+        // A single crank pays the signature fee of its worker.
+        assert_eq!(
+            fee_share(
+                &[(crate::ID, vec![worker], crank_data.clone())],
+                &queue(None, None)
+            )
+            .unwrap(),
+            5_000
+        );
+
+        // Other signers of a crank are not charged.
+        assert_eq!(
+            fee_share(
+                &[(
+                    crate::ID,
+                    vec![worker, Pubkey::new_unique()],
+                    crank_data.clone()
+                )],
+                &queue(None, None)
+            )
+            .unwrap(),
+            5_000
+        );
+
+        // Other instructions may not require signatures.
+        assert!(fee_share(
+            &[
+                (crate::ID, vec![worker], crank_data.clone()),
+                (Pubkey::new_unique(), vec![worker], vec![]),
+            ],
+            &queue(None, None)
+        )
+        .is_err());
+        assert_eq!(
+            fee_share(
+                &[
+                    (crate::ID, vec![worker], crank_data.clone()),
+                    (Pubkey::new_unique(), vec![], vec![]),
+                ],
+                &queue(None, None)
+            )
+            .unwrap(),
+            5_000
+        );
+
+        // The fee is split across the cranks and capped at the queue's compute unit price.
+        assert_eq!(
+            fee_share(
+                &[
+                    (
+                        COMPUTE_BUDGET_PROGRAM_ID,
+                        vec![],
+                        set_compute_unit_limit_data(400_000)
+                    ),
+                    (
+                        COMPUTE_BUDGET_PROGRAM_ID,
+                        vec![],
+                        set_compute_unit_price_data(10_000)
+                    ),
+                    (crate::ID, vec![worker], crank_data.clone()),
+                    (crate::ID, vec![worker], crank_data.clone()),
+                ],
+                &queue(None, Some(5_000))
+            )
+            .unwrap(),
+            3_500
+        );
+
+        // Without a requested limit, the default limit per instruction applies, capped at the queue's limit.
+        let instructions = [
+            (
+                COMPUTE_BUDGET_PROGRAM_ID,
+                vec![],
+                set_compute_unit_price_data(1_000_000),
+            ),
+            (crate::ID, vec![worker], crank_data),
+        ];
+        assert_eq!(
+            fee_share(&instructions, &queue(None, Some(1_000_000))).unwrap(),
+            205_000
+        );
+        assert_eq!(
+            fee_share(&instructions, &queue(Some(100_000), Some(1_000_000))).unwrap(),
+            105_000
+        );
+
+        // Priority fees are not charged to queues without a compute unit price.
+        assert_eq!(fee_share(&instructions, &queue(None, None)).unwrap(), 5_000);
+    }
+
+    #[test]
+    fn test_default_compute_unit_limit() {
+        assert_eq!(default_compute_unit_limit(0), 0);
+        assert_eq!(default_compute_unit_limit(3), 600_000);
+        assert_eq!(default_compute_unit_limit(8), MAX_COMPUTE_UNIT_LIMIT);
+    }
+
+    #[test]
+    fn test_split_transaction_fee() {
+        assert_eq!(split_transaction_fee(0, 0, 1, 5_000, 1), 5_000);
+        assert_eq!(split_transaction_fee(0, 0, 2, 5_000, 1), 2_500);
+        assert_eq!(split_transaction_fee(0, 0, 3, 5_000, 1), 1_667);
+        assert_eq!(split_transaction_fee(0, 0, 1, 5_000, 2), 10_000);
+
+        // Priority fees round up to the nearest lamport.
+        assert_eq!(split_transaction_fee(200_000, 1, 1, 5_000, 1), 5_001);
+        assert_eq!(split_transaction_fee(200_000, 10, 1, 5_000, 1), 5_002);
+
+        // A transaction without cranks is charged as if it had one.
+        assert_eq!(split_transaction_fee(0, 0, 0, 5_000, 1), 5_000);
+    }
 }
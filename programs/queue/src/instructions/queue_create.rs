@@ -1,5 +1,5 @@
 use {
-    crate::objects::*,
+    crate::{errors::ClockworkError, events::QueueCreated, objects::*},
     anchor_lang::{prelude::*, solana_program::system_program},
     std::mem::size_of,
 };
@@ -7,12 +7,16 @@ use {
 
 /// Accounts required by the `queue_create` instruction.
 #[derive(Accounts)]
-#[instruction(id: String, kickoff_instruction: InstructionData, trigger: Trigger)]
+#[instruction(settings: QueueCreateSettings)]
 pub struct QueueCreate<'info> {
     /// The authority (owner) of the queue.
     #[account()]
     pub authority: Signer<'info>,
 
+    /// The program config account.
+    #[account(address = Config::pubkey())]
+    pub config: Account<'info, Config>,
+
     /// The payer for account initializations. 
     #[account(mut)]
     pub payer: Signer<'info>,
@@ -23,16 +27,17 @@ pub struct QueueCreate<'info> {
         seeds = [
             SEED_QUEUE,
             authority.key().as_ref(),
-            id.as_bytes(),
+            settings.id.as_bytes(),
         ],
         bump,
         payer = payer,
         space = vec![
             8, 
             size_of::<Queue>(), 
-            id.as_bytes().len(),
-            kickoff_instruction.try_to_vec()?.len(),  
-            trigger.try_to_vec()?.len()
+            (settings.exec_history_capacity as usize).saturating_mul(size_of::<ExecRecord>()),
+            settings.id.as_bytes().len(),
+            settings.kickoff_instructions.try_to_vec()?.len(),  
+            settings.trigger.try_to_vec()?.len()
         ].iter().sum()
     )]
     pub queue: Account<'info, Queue>,
@@ -42,13 +47,36 @@ pub struct QueueCreate<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(ctx: Context<QueueCreate>, id: String, kickoff_instruction: InstructionData, trigger: Trigger) -> Result<()> {
+pub fn handler(ctx: Context<QueueCreate>, settings: QueueCreateSettings) -> Result<()> {
     // Get accounts
     let authority = &ctx.accounts.authority;
+    let config = &ctx.accounts.config;
     let queue = &mut ctx.accounts.queue;
 
+    // Verify the execution history capacity is within the allowed range
+    require!(settings.exec_history_capacity.le(&MAX_EXEC_HISTORY_CAPACITY), ClockworkError::ExecHistoryCapacityTooLarge);
+
+    // Verify the trigger can be evaluated
+    settings.trigger.validate()?;
+
+    // If provided, verify the worker pool is a pool account
+    if let Some(pool) = settings.pool {
+        verify_pool(ctx.remaining_accounts, pool)?;
+    }
+
     // Initialize the queue
-    queue.init(authority.key(), id, kickoff_instruction, trigger)?;
+    queue.init(authority.key(), settings)?;
+
+    // Verify the default rate limit is within the config's maximum
+    require!(queue.rate_limit.le(&config.max_rate_limit), ClockworkError::RateLimitTooLarge);
+
+    // Emit an event
+    emit!(QueueCreated {
+        authority: queue.authority,
+        id: queue.id.clone(),
+        queue: queue.key(),
+        trigger: queue.trigger.clone(),
+    });
 
     Ok(())
 }
@@ -1,4 +1,7 @@
-use {crate::objects::*, anchor_lang::prelude::*};
+use {
+    crate::{events::QueueDeleted, objects::*},
+    anchor_lang::prelude::*,
+};
 
 /// Accounts required by the `queue_delete` instruction.
 #[derive(Accounts)]
@@ -21,11 +24,29 @@ pub struct QueueDelete<'info> {
         ],
         bump,
         has_one = authority,
-        close = close_to
     )]
     pub queue: Account<'info, Queue>,
 }
 
-pub fn handler(_ctx: Context<QueueDelete>) -> Result<()> {
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, QueueDelete<'info>>) -> Result<()> {
+    // Get accounts
+    let close_to = &ctx.accounts.close_to;
+    let queue = &mut ctx.accounts.queue;
+
+    // Close the queue, holding back the sponsors' unspent lamports and sweeping its tokens
+    let bump = ctx.bumps.get("queue").unwrap();
+    close_queue(
+        ctx.remaining_accounts,
+        *bump,
+        close_to.to_account_info(),
+        queue,
+    )?;
+
+    // Emit an event
+    emit!(QueueDeleted {
+        close_to: close_to.key(),
+        queue: queue.key(),
+    });
+
     Ok(())
 }
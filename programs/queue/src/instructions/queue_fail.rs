@@ -0,0 +1,109 @@
+use {
+    crate::{errors::*, events::QueueFailed, objects::*},
+    anchor_lang::prelude::*,
+    clockwork_pool_program::objects::Pool,
+};
+
+/// Accounts required by the `queue_fail` instruction.
+#[derive(Accounts)]
+#[instruction(attempted_at: u64, data_hash: Option<u64>)]
+pub struct QueueFail<'info> {
+    /// The program config account.
+    #[account(address = Config::pubkey())]
+    pub config: Box<Account<'info, Config>>,
+
+    /// The worker pool allowed to crank the queue.
+    #[account(address = queue.pool.unwrap_or(config.worker_pool))]
+    pub pool: Box<Account<'info, Pool>>,
+
+    /// The failing queue.
+    #[account(
+        mut,
+        seeds = [
+            SEED_QUEUE,
+            queue.authority.as_ref(),
+            queue.id.as_bytes(),
+        ],
+        bump,
+        constraint = !queue.paused @ ClockworkError::PausedQueue
+    )]
+    pub queue: Box<Account<'info, Queue>>,
+
+    /// The worker reporting the failure.
+    #[account(mut)]
+    pub worker: Signer<'info>,
+}
+
+pub fn handler<'info>(
+    ctx: Context<'_, '_, '_, 'info, QueueFail<'info>>,
+    attempted_at: u64,
+    data_hash: Option<u64>,
+) -> Result<()> {
+    // Get accounts
+    let pool = &ctx.accounts.pool;
+    let queue = &mut ctx.accounts.queue;
+    let worker = &ctx.accounts.worker;
+
+    // Only workers in the pool may report failures.
+    require!(
+        pool.workers.contains(&worker.key()),
+        ClockworkError::UnauthorizedWorker
+    );
+
+    // A failed inner instruction aborts the whole transaction, so the failure itself cannot be observed on-chain.
+    // Instead, the report is bound to the slot of the failed crank attempt, which must be recent, made once the
+    // retry backoff had elapsed, and not followed by a successful crank.
+    let clock = Clock::get().unwrap();
+    queue.verify_failed_attempt(attempted_at, clock.slot)?;
+
+    // Verify the queue is due to be cranked, so a crank would have reached its instructions. The checks a crank
+    // makes before invoking the queue's instructions are re-run here.
+    if let Some(exec_context) = queue.exec_context {
+        require!(
+            exec_context.last_crank_at != clock.slot
+                || exec_context.cranks_since_slot < queue.rate_limit,
+            ClockworkError::RateLimitExeceeded
+        );
+    }
+    let exec_context = if queue.next_instructions.is_empty() {
+        require!(
+            !queue.is_expired(clock.unix_timestamp),
+            ClockworkError::QueueExpired
+        );
+        let (window_exec_count, window_started_at) = queue.count_exec_in_window(&clock)?;
+
+        // The predicate of a condition queue may be the instruction which failed, so it is not re-run here.
+        let trigger_context = match queue.trigger {
+            Trigger::Condition { .. } => TriggerContext::Condition {
+                started_at: clock.slot,
+            },
+            _ => queue.verify_trigger(ctx.remaining_accounts, data_hash, worker)?,
+        };
+
+        // The exec context to run the queue's error instruction in, if this failure exhausts its retries.
+        Some(ExecContext {
+            cranks_since_slot: 0,
+            last_crank_at: clock.slot,
+            trigger_context: Some(trigger_context),
+            window_exec_count,
+            window_started_at,
+        })
+    } else {
+        None
+    };
+
+    // Record the failure
+    queue.fail(clock.slot, exec_context);
+    queue.realloc()?;
+
+    // Emit an event
+    emit!(QueueFailed {
+        consecutive_failures: queue.consecutive_failures,
+        paused: queue.paused,
+        queue: queue.key(),
+        retry_at: queue.retry_at(),
+        worker: worker.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,107 @@
+use {
+    crate::{events::QueueFunded, objects::*},
+    anchor_lang::{
+        prelude::*,
+        solana_program::system_program,
+        system_program::{transfer, Transfer},
+    },
+    std::mem::size_of,
+};
+
+/// Accounts required by the `queue_fund` instruction.
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct QueueFund<'info> {
+    /// The queue's funding account, recording its sponsors.
+    #[account(
+        init_if_needed,
+        seeds = [
+            SEED_FUNDING,
+            queue.key().as_ref(),
+            queue.created_at.slot.to_le_bytes().as_ref(),
+        ],
+        bump,
+        payer = sponsor,
+        space = 8 + size_of::<Funding>() + size_of::<Sponsor>(),
+    )]
+    pub funding: Account<'info, Funding>,
+
+    /// The queue to be funded.
+    #[account(
+        mut,
+        seeds = [
+            SEED_QUEUE,
+            queue.authority.as_ref(),
+            queue.id.as_bytes(),
+        ],
+        bump,
+    )]
+    pub queue: Account<'info, Queue>,
+
+    /// The sponsor funding the queue.
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+    /// The Solana system program
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<QueueFund>, amount: u64) -> Result<()> {
+    // Get accounts
+    let funding = &mut ctx.accounts.funding;
+    let queue = &mut ctx.accounts.queue;
+    let sponsor = &ctx.accounts.sponsor;
+    let system_program = &ctx.accounts.system_program;
+
+    // Initialize the funding account, if needed
+    if funding.queue.ne(&queue.key()) {
+        funding.init(queue.key(), queue.created_at.slot)?;
+    }
+
+    // Record the sponsor's contribution
+    funding.contribute(amount, sponsor.key())?;
+    queue.sponsored_lamports = queue.sponsored_lamports.checked_add(amount).unwrap();
+
+    // Transfer the contribution from the sponsor to the queue
+    transfer(
+        CpiContext::new(
+            system_program.to_account_info(),
+            Transfer {
+                from: sponsor.to_account_info(),
+                to: queue.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    // Reallocate mem for the funding account
+    funding.realloc()?;
+
+    // If lamports are required to maintain rent-exemption, pay them
+    let data_len = funding.to_account_info().data_len();
+    let minimum_rent = Rent::get().unwrap().minimum_balance(data_len);
+    if minimum_rent > funding.to_account_info().lamports() {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: sponsor.to_account_info(),
+                    to: funding.to_account_info(),
+                },
+            ),
+            minimum_rent
+                .checked_sub(funding.to_account_info().lamports())
+                .unwrap(),
+        )?;
+    }
+
+    // Emit an event
+    emit!(QueueFunded {
+        amount,
+        queue: queue.key(),
+        sponsor: sponsor.key(),
+    });
+
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use {
+    crate::objects::*,
+    anchor_lang::{
+        prelude::*,
+        solana_program::system_program,
+        system_program::{transfer, Transfer},
+    },
+};
+
+/// Accounts required by the `queue_migrate` instruction.
+#[derive(Accounts)]
+pub struct QueueMigrate<'info> {
+    /// The authority (owner) of the queue.
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// The queue to migrate, which must still have its original layout.
+    /// CHECK: This account is parsed with the original queue layout and rewritten with the current one.
+    #[account(mut, owner = crate::ID)]
+    pub queue: UncheckedAccount<'info>,
+
+    /// The Solana system program.
+    #[account(address = system_program::ID)]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler<'info>(ctx: Context<'_, '_, '_, 'info, QueueMigrate<'info>>) -> Result<()> {
+    // Get accounts
+    let authority = &ctx.accounts.authority;
+    let queue = &ctx.accounts.queue;
+    let system_program = &ctx.accounts.system_program;
+
+    // Parse the original layout, and verify the signer is the queue's authority
+    let queue_info = queue.to_account_info();
+    let legacy_queue = LegacyQueue::try_parse(&queue_info.try_borrow_data()?)?;
+    require_keys_eq!(
+        legacy_queue.authority,
+        authority.key(),
+        ErrorCode::ConstraintHasOne
+    );
+
+    // Convert the queue to the current layout, rehashing the data of its watched account
+    let migrated_queue = legacy_queue.migrate(ctx.remaining_accounts)?;
+
+    // Reallocate mem for the queue account
+    let data_len =
+        8 + migrated_queue.try_to_vec()?.len() + migrated_queue.exec_history_reserved_len();
+    queue_info.realloc(data_len, false)?;
+
+    // If lamports are required to maintain rent-exemption, pay them
+    let minimum_rent = Rent::get().unwrap().minimum_balance(data_len);
+    if minimum_rent > queue.lamports() {
+        transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: authority.to_account_info(),
+                    to: queue.to_account_info(),
+                },
+            ),
+            minimum_rent.checked_sub(queue.lamports()).unwrap(),
+        )?;
+    }
+
+    // Write the queue with the current layout
+    migrated_queue.try_serialize(&mut &mut queue_info.try_borrow_mut_data()?[..])?;
+
+    Ok(())
+}
@@ -1,4 +1,7 @@
-use {crate::objects::*, anchor_lang::prelude::*};
+use {
+    crate::{events::QueuePaused, objects::*},
+    anchor_lang::prelude::*,
+};
 
 /// Accounts required by the `queue_delete` instruction.
 #[derive(Accounts)]
@@ -28,5 +31,8 @@ pub fn handler(ctx: Context<QueuePause>) -> Result<()> {
     // Pause the queue
     queue.paused = true;
 
+    // Emit an event
+    emit!(QueuePaused { queue: queue.key() });
+
     Ok(())
 }
@@ -1,4 +1,7 @@
-use {crate::objects::*, anchor_lang::prelude::*};
+use {
+    crate::{events::QueueResumed, objects::*},
+    anchor_lang::prelude::*,
+};
 
 /// Accounts required by the `queue_resume` instruction.
 #[derive(Accounts)]
@@ -25,32 +28,57 @@ pub fn handler(ctx: Context<QueueResume>) -> Result<()> {
     // Get accounts
     let queue = &mut ctx.accounts.queue;
 
-    // Resume the queue
+    // Resume the queue and reset its failure count
     queue.paused = false;
+    queue.consecutive_failures = 0;
+    queue.last_failure_at = None;
 
     // Update the exec context
     match queue.exec_context {
         None => {}
         Some(exec_context) => {
             match exec_context.trigger_context {
-                TriggerContext::Account { data_hash: _ } => {
+                None => {
                     // Nothing to do
                 }
-                TriggerContext::Cron { started_at: _ } => {
+                Some(TriggerContext::Account { data_hash: _ }) => {
+                    // Nothing to do
+                }
+                Some(TriggerContext::Accounts { data_hashes: _ }) => {
+                    // Nothing to do
+                }
+                Some(TriggerContext::Condition { started_at: _ }) => {
+                    // Nothing to do
+                }
+                Some(TriggerContext::Queue {
+                    completion_count: _,
+                }) => {
+                    // Nothing to do
+                }
+                Some(TriggerContext::Cron { started_at: _ }) => {
                     // Jump ahead to the current timestamp
                     queue.exec_context = Some(ExecContext {
-                        trigger_context: TriggerContext::Cron {
+                        trigger_context: Some(TriggerContext::Cron {
                             started_at: Clock::get().unwrap().unix_timestamp,
-                        },
+                        }),
                         ..exec_context
                     });
                 }
-                TriggerContext::Immediate => {
+                Some(TriggerContext::Epoch { started_at: _ }) => {
+                    // Nothing to do
+                }
+                Some(TriggerContext::Immediate) => {
+                    // Nothing to do
+                }
+                Some(TriggerContext::Slot { started_at: _ }) => {
                     // Nothing to do
                 }
             }
         }
     }
 
+    // Emit an event
+    emit!(QueueResumed { queue: queue.key() });
+
     Ok(())
 }
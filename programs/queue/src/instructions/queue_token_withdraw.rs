@@ -0,0 +1,85 @@
+use {
+    crate::{events::QueueTokensWithdrawn, objects::*},
+    anchor_lang::prelude::*,
+    anchor_spl::token::{transfer, Mint, Token, TokenAccount, Transfer},
+};
+
+/// Accounts required by the `queue_token_withdraw` instruction.
+#[derive(Accounts)]
+#[instruction(amount: u64)]
+pub struct QueueTokenWithdraw<'info> {
+    /// The authority (owner) of the queue.
+    #[account()]
+    pub authority: Signer<'info>,
+
+    /// The mint of the tokens to withdraw.
+    #[account()]
+    pub mint: Account<'info, Mint>,
+
+    /// The token account to withdraw tokens to.
+    #[account(mut, token::mint = mint)]
+    pub pay_to: Account<'info, TokenAccount>,
+
+    /// The queue to withdraw tokens from.
+    #[account(
+        seeds = [
+            SEED_QUEUE,
+            queue.authority.as_ref(),
+            queue.id.as_bytes(),
+        ],
+        bump,
+        has_one = authority,
+    )]
+    pub queue: Account<'info, Queue>,
+
+    /// The queue's token account.
+    #[account(
+        mut,
+        associated_token::authority = queue,
+        associated_token::mint = mint,
+    )]
+    pub queue_tokens: Account<'info, TokenAccount>,
+
+    /// The SPL token program.
+    #[account(address = anchor_spl::token::ID)]
+    pub token_program: Program<'info, Token>,
+}
+
+pub fn handler(ctx: Context<QueueTokenWithdraw>, amount: u64) -> Result<()> {
+    // Get accounts
+    let mint = &ctx.accounts.mint;
+    let pay_to = &ctx.accounts.pay_to;
+    let queue = &ctx.accounts.queue;
+    let queue_tokens = &ctx.accounts.queue_tokens;
+    let token_program = &ctx.accounts.token_program;
+
+    // Transfer tokens from the queue's token account to the pay_to account
+    let bump = *ctx.bumps.get("queue").unwrap();
+    transfer(
+        CpiContext::new_with_signer(
+            token_program.to_account_info(),
+            Transfer {
+                from: queue_tokens.to_account_info(),
+                to: pay_to.to_account_info(),
+                authority: queue.to_account_info(),
+            },
+            &[&[
+                SEED_QUEUE,
+                queue.authority.as_ref(),
+                queue.id.as_bytes(),
+                &[bump],
+            ]],
+        ),
+        amount,
+    )?;
+
+    // Emit an event
+    emit!(QueueTokensWithdrawn {
+        amount,
+        mint: mint.key(),
+        pay_to: pay_to.key(),
+        queue: queue.key(),
+    });
+
+    Ok(())
+}
@@ -1,22 +1,20 @@
 use {
-    crate::{errors::ClockworkError, objects::*},
+    crate::{errors::ClockworkError, events::QueueUpdated, objects::*},
     anchor_lang::{prelude::*, system_program::{transfer, Transfer}, solana_program::system_program},
 };
 
-const MAX_RATE_LIMIT: u64 = 32; 
-
 /// Accounts required by the `queue_update` instruction.
 #[derive(Accounts)]
-#[instruction(
-    kickoff_instruction: Option<InstructionData>, 
-    rate_limit: Option<u64>, 
-    trigger: Option<Trigger>
-)]
+#[instruction(settings: QueueSettings)]
 pub struct QueueUpdate<'info> {
     /// The authority (owner) of the queue.
     #[account(mut)]
     pub authority: Signer<'info>,
 
+    /// The program config account.
+    #[account(address = Config::pubkey())]
+    pub config: Account<'info, Config>,
+
     /// The queue to be updated.
     #[account(
         mut,
@@ -35,42 +33,132 @@ pub struct QueueUpdate<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<QueueUpdate>, 
-    kickoff_instruction: Option<InstructionData>, 
-    rate_limit: Option<u64>, 
-    trigger: Option<Trigger>
-) -> Result<()> {
-    
+pub fn handler(ctx: Context<QueueUpdate>, settings: QueueSettings) -> Result<()> {
     // Get accounts
     let authority = &ctx.accounts.authority;
+    let config = &ctx.accounts.config;
     let queue = &mut ctx.accounts.queue;
     let system_program = &ctx.accounts.system_program;
 
-    // If provided, update the queue's first instruction
-    if let Some(kickoff_instruction) = kickoff_instruction {
-        queue.kickoff_instruction = kickoff_instruction;
+    // If provided, update the address lookup tables
+    if let Some(address_lookup_tables) = settings.address_lookup_tables {
+        queue.address_lookup_tables = address_lookup_tables;
+    }
+
+    // If provided, update the auto close setting
+    if let Some(auto_close) = settings.auto_close {
+        queue.auto_close = auto_close;
+    }
+
+    // If provided, update the compute unit limit
+    if let Some(compute_unit_limit) = settings.compute_unit_limit {
+        if let Some(compute_unit_limit) = compute_unit_limit {
+            require!(compute_unit_limit.le(&MAX_COMPUTE_UNIT_LIMIT), ClockworkError::ComputeUnitLimitTooLarge);
+        }
+        queue.compute_unit_limit = compute_unit_limit;
+    }
+
+    // If provided, update the compute unit price
+    if let Some(compute_unit_price) = settings.compute_unit_price {
+        queue.compute_unit_price = compute_unit_price;
+    }
+
+    // If provided, update the instruction data placeholders
+    if let Some(data_placeholders) = settings.data_placeholders {
+        queue.data_placeholders = data_placeholders;
+    }
+
+    // If provided, update the queue's error instruction
+    if let Some(error_instruction) = settings.error_instruction {
+        queue.error_instruction = error_instruction;
+    }
+
+    // If provided, update the execution history capacity and evict any records which no longer fit
+    if let Some(exec_history_capacity) = settings.exec_history_capacity {
+        require!(exec_history_capacity.le(&MAX_EXEC_HISTORY_CAPACITY), ClockworkError::ExecHistoryCapacityTooLarge);
+        queue.exec_history_capacity = exec_history_capacity;
+        queue.truncate_exec_history();
+    }
+
+    // If provided, update the execution rate limit
+    if let Some(exec_rate_limit) = settings.exec_rate_limit {
+        if let Some(exec_rate_limit) = exec_rate_limit {
+            require!(exec_rate_limit.is_valid(), ClockworkError::InvalidExecRateLimit);
+        }
+        queue.exec_rate_limit = exec_rate_limit;
+    }
+
+    // If provided, update the expiry
+    if let Some(expires_at) = settings.expires_at {
+        queue.expires_at = expires_at;
+    }
+
+    // If provided, update the fee bid
+    if let Some(fee_bid) = settings.fee_bid {
+        if let Some(fee_bid) = fee_bid {
+            require!(fee_bid.ge(&config.crank_fee), ClockworkError::FeeBidTooLow);
+        }
+        queue.fee_bid = fee_bid;
+    }
+
+    // If provided, update the fee mint
+    if let Some(fee_mint) = settings.fee_mint {
+        if let Some(fee_mint) = fee_mint {
+            require!(config.fee_mint.map(|f| f.mint).eq(&Some(fee_mint)), ClockworkError::InvalidFeeMint);
+        }
+        queue.fee_mint = fee_mint;
+    }
+
+    // If provided, update the queue's kickoff instructions
+    if let Some(kickoff_instructions) = settings.kickoff_instructions {
+        queue.kickoff_instructions = kickoff_instructions;
+    }
+
+    // If provided, update the low-balance threshold
+    if let Some(low_balance_threshold) = settings.low_balance_threshold {
+        queue.low_balance_threshold = low_balance_threshold;
+    }
+
+    // If provided, update the max executions
+    if let Some(max_executions) = settings.max_executions {
+        queue.max_executions = max_executions;
+    }
+
+    // If provided, update the max retries
+    if let Some(max_retries) = settings.max_retries {
+        queue.max_retries = max_retries;
+    }
+
+    // If provided, verify the worker pool is a pool account and update it
+    if let Some(pool) = settings.pool {
+        if let Some(pool) = pool {
+            verify_pool(ctx.remaining_accounts, pool)?;
+        }
+        queue.pool = pool;
     }
 
     // If provided, update the rate_limit
-    if let Some(rate_limit) = rate_limit {
-        require!(rate_limit.le(&MAX_RATE_LIMIT), ClockworkError::RateLimitTooLarge);
+    if let Some(rate_limit) = settings.rate_limit {
+        require!(rate_limit.le(&config.max_rate_limit), ClockworkError::RateLimitTooLarge);
         queue.rate_limit = rate_limit;
     }
 
-    // If provided, update the queue's trigger and reset the exec context
-    if let Some(trigger) = trigger {
+    // If provided, update the queue's trigger and clear the trigger context, keeping the rate limit window
+    if let Some(trigger) = settings.trigger {
+        trigger.validate()?;
         queue.trigger = trigger;
-        queue.exec_context = None;
+        queue.exec_context = queue.exec_context.map(|exec_context| ExecContext {
+            trigger_context: None,
+            ..exec_context
+        });
     }
 
     // Reallocate mem for the queue account
     queue.realloc()?;
 
-    // If lamports are required to maintain rent-exemption, pay them
-    let data_len = 8 + queue.try_to_vec()?.len();
-    let minimum_rent = Rent::get().unwrap().minimum_balance(data_len);
-    if minimum_rent > queue.to_account_info().lamports() {
+    // If lamports are required to maintain rent-exemption without spending the sponsors' lamports, pay them
+    let rent_shortfall = queue.rent_shortfall()?;
+    if rent_shortfall > 0 {
         transfer(
             CpiContext::new(
                 system_program.to_account_info(),
@@ -79,11 +167,16 @@ pub fn handler(
                     to: queue.to_account_info(),
                 },
             ),
-            minimum_rent
-                .checked_sub(queue.to_account_info().lamports())
-                .unwrap(),
+            rent_shortfall,
         )?;
     }
 
+    // Emit an event
+    emit!(QueueUpdated {
+        queue: queue.key(),
+        rate_limit: queue.rate_limit,
+        trigger: queue.trigger.clone(),
+    });
+
     Ok(())
 }
\ No newline at end of file
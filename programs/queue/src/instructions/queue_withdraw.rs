@@ -1,4 +1,7 @@
-use {crate::objects::*, anchor_lang::prelude::*};
+use {
+    crate::{errors::ClockworkError, events::QueueWithdrawn, objects::*},
+    anchor_lang::prelude::*,
+};
 
 /// Accounts required by the `queue_withdraw` instruction.
 #[derive(Accounts)]
@@ -31,17 +34,29 @@ pub fn handler(ctx: Context<QueueWithdraw>, amount: u64) -> Result<()> {
     let pay_to = &mut ctx.accounts.pay_to;
     let queue = &mut ctx.accounts.queue;
 
+    // Verify the withdrawal leaves the lamports held back for the queue's sponsors
+    let withdrawable_balance = queue
+        .spendable_balance()?
+        .saturating_sub(queue.sponsored_lamports);
+    require!(
+        amount.le(&withdrawable_balance),
+        ClockworkError::InsufficientFunds
+    );
+
     // Withdraw balance from queue to the pay_to account
-    **queue.to_account_info().try_borrow_mut_lamports()? = queue
-        .to_account_info()
-        .lamports()
-        .checked_sub(amount)
-        .unwrap();
+    queue.debit(amount)?;
     **pay_to.to_account_info().try_borrow_mut_lamports()? = pay_to
         .to_account_info()
         .lamports()
         .checked_add(amount)
         .unwrap();
 
+    // Emit an event
+    emit!(QueueWithdrawn {
+        amount,
+        pay_to: pay_to.key(),
+        queue: queue.key(),
+    });
+
     Ok(())
 }
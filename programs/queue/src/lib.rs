@@ -3,6 +3,7 @@
 //! to schedule transactions and automate smart-contracts without relying on centralized infrastructure.
 
 pub mod errors;
+pub mod events;
 pub mod objects;
 pub mod utils;
 
@@ -19,36 +20,112 @@ declare_id!("AfWZ9PyWW3E3G1TVYkKCe5w6BUC6w5Rim1DcmbAeaU3H");
 pub mod queue_program {
     use super::*;
 
+    /// Admin instruction for reallocating the config account to hold the fields appended since it was initialized.
+    pub fn config_migrate(ctx: Context<ConfigMigrate>) -> Result<()> {
+        config_migrate::handler(ctx)
+    }
+
     /// Admin instruction for updating the program config.
     pub fn config_update(ctx: Context<ConfigUpdate>, settings: ConfigSettings) -> Result<()> {
         config_update::handler(ctx, settings)
     }
 
+    /// Allows a worker to claim the lamports escrowed to them for cranking queues.
+    pub fn fee_claim(ctx: Context<FeeClaim>, amount: u64) -> Result<()> {
+        fee_claim::handler(ctx, amount)
+    }
+
+    /// Allows a worker to claim the tokens escrowed to them for cranking queues which pay fees in tokens.
+    pub fn fee_token_claim(ctx: Context<FeeTokenClaim>, amount: u64) -> Result<()> {
+        fee_token_claim::handler(ctx, amount)
+    }
+
+    /// Admin instruction for claiming the tokens withheld from workers for submitting spam.
+    pub fn fee_token_withholding_claim(
+        ctx: Context<FeeTokenWithholdingClaim>,
+        amount: u64,
+    ) -> Result<()> {
+        fee_token_withholding_claim::handler(ctx, amount)
+    }
+
+    /// Admin instruction for claiming the lamports withheld from a worker for submitting spam.
+    pub fn fee_withholding_claim(ctx: Context<FeeWithholdingClaim>, amount: u64) -> Result<()> {
+        fee_withholding_claim::handler(ctx, amount)
+    }
+
+    /// Allows a sponsor to reclaim their share of a deleted queue's unspent funding.
+    pub fn funding_reclaim(ctx: Context<FundingReclaim>) -> Result<()> {
+        funding_reclaim::handler(ctx)
+    }
+
     /// Admin instruction for initializing the program after deployment.
     pub fn initialize(ctx: Context<Initialize>, worker_pool: Pubkey) -> Result<()> {
         initialize::handler(ctx, worker_pool)
     }
 
-    /// Cranks a transaction queue.
-    pub fn queue_crank(ctx: Context<QueueCrank>, data_hash: Option<u64>) -> Result<()> {
-        queue_crank::handler(ctx, data_hash)
+    /// Closes an instruction buffer and returns the lamports to the owner.
+    pub fn instruction_buffer_close(ctx: Context<InstructionBufferClose>) -> Result<()> {
+        instruction_buffer_close::handler(ctx)
     }
 
-    /// Creates a new transaction queue.
-    pub fn queue_create(
-        ctx: Context<QueueCreate>,
+    /// Creates a new instruction buffer to write a large instruction into.
+    pub fn instruction_buffer_create(
+        ctx: Context<InstructionBufferCreate>,
         id: String,
-        kickoff_instruction: InstructionData,
-        trigger: Trigger,
+        size: u64,
+    ) -> Result<()> {
+        instruction_buffer_create::handler(ctx, id, size)
+    }
+
+    /// Writes a chunk of a serialized instruction into an instruction buffer.
+    pub fn instruction_buffer_write(
+        ctx: Context<InstructionBufferWrite>,
+        data: Vec<u8>,
+        offset: u64,
     ) -> Result<()> {
-        queue_create::handler(ctx, id, kickoff_instruction, trigger)
+        instruction_buffer_write::handler(ctx, data, offset)
+    }
+
+    /// Cranks a transaction queue.
+    pub fn queue_crank<'info>(
+        ctx: Context<'_, '_, '_, 'info, QueueCrank<'info>>,
+        data_hash: Option<u64>,
+        lamports_per_signature: u64,
+    ) -> Result<()> {
+        queue_crank::handler(ctx, data_hash, lamports_per_signature)
+    }
+
+    /// Creates a new transaction queue.
+    pub fn queue_create(ctx: Context<QueueCreate>, settings: QueueCreateSettings) -> Result<()> {
+        queue_create::handler(ctx, settings)
     }
 
     /// Closes an existing queue account and returns the lamports to the owner.
-    pub fn queue_delete(ctx: Context<QueueDelete>) -> Result<()> {
+    pub fn queue_delete<'info>(ctx: Context<'_, '_, '_, 'info, QueueDelete<'info>>) -> Result<()> {
         queue_delete::handler(ctx)
     }
 
+    /// Allows a worker to report that a queue's crank failed.
+    pub fn queue_fail<'info>(
+        ctx: Context<'_, '_, '_, 'info, QueueFail<'info>>,
+        attempted_at: u64,
+        data_hash: Option<u64>,
+    ) -> Result<()> {
+        queue_fail::handler(ctx, attempted_at, data_hash)
+    }
+
+    /// Allows a sponsor to fund a queue, recording their contribution.
+    pub fn queue_fund(ctx: Context<QueueFund>, amount: u64) -> Result<()> {
+        queue_fund::handler(ctx, amount)
+    }
+
+    /// Converts a queue from its original layout to the current one.
+    pub fn queue_migrate<'info>(
+        ctx: Context<'_, '_, '_, 'info, QueueMigrate<'info>>,
+    ) -> Result<()> {
+        queue_migrate::handler(ctx)
+    }
+
     /// Pauses an active queue.
     pub fn queue_pause(ctx: Context<QueuePause>) -> Result<()> {
         queue_pause::handler(ctx)
@@ -59,14 +136,14 @@ pub mod queue_program {
         queue_resume::handler(ctx)
     }
 
+    /// Allows an owner to withdraw from a queue's token balance.
+    pub fn queue_token_withdraw(ctx: Context<QueueTokenWithdraw>, amount: u64) -> Result<()> {
+        queue_token_withdraw::handler(ctx, amount)
+    }
+
     /// Allows an owner to update the mutable properties of a queue.
-    pub fn queue_update(
-        ctx: Context<QueueUpdate>,
-        kickoff_instruction: Option<InstructionData>,
-        rate_limit: Option<u64>,
-        trigger: Option<Trigger>,
-    ) -> Result<()> {
-        queue_update::handler(ctx, kickoff_instruction, rate_limit, trigger)
+    pub fn queue_update(ctx: Context<QueueUpdate>, settings: QueueSettings) -> Result<()> {
+        queue_update::handler(ctx, settings)
     }
 
     /// Allows an owner to withdraw from a queue's lamport balance.
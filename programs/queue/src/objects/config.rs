@@ -6,8 +6,11 @@ use {
 pub const SEED_CONFIG: &[u8] = b"config";
 
 static DEFAULT_CRANK_FEE: u64 = 1_000;
+static DEFAULT_MAX_LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+static DEFAULT_MAX_RATE_LIMIT: u64 = 32;
 
-/// The config object, recording the config values of a specific Clockwork queue program deployment.
+/// The config object, recording the config values of a specific Clockwork queue program deployment. New fields
+/// are appended to the end, so configs initialized before them can be migrated with `config_migrate`.
 #[account]
 #[derive(Debug)]
 pub struct Config {
@@ -20,6 +23,15 @@ pub struct Config {
 
     /// The public address of the worker pool.
     pub worker_pool: Pubkey,
+
+    /// The maximum rate limit, in cranks per slot, a queue may be configured with.
+    pub max_rate_limit: u64,
+
+    /// The SPL token mint queues may pay crank fees in, and the fee paid per crank in that mint.
+    pub fee_mint: Option<FeeMint>,
+
+    /// The maximum signature fee, in lamports, workers may be reimbursed at per transaction signature.
+    pub max_lamports_per_signature: u64,
 }
 
 impl Config {
@@ -43,15 +55,33 @@ pub struct ConfigSettings {
     pub admin: Pubkey,
     /// The fee paid out to workers by users per successful crank.
     pub crank_fee: u64,
+    /// The SPL token mint queues may pay crank fees in, and the fee paid per crank in that mint.
+    pub fee_mint: Option<FeeMint>,
+    /// The maximum signature fee, in lamports, workers may be reimbursed at per transaction signature.
+    pub max_lamports_per_signature: u64,
+    /// The maximum rate limit, in cranks per slot, a queue may be configured with.
+    pub max_rate_limit: u64,
     /// The public address of the worker pool.
     pub worker_pool: Pubkey,
 }
 
+/// An SPL token mint whitelisted for paying crank fees.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct FeeMint {
+    /// The fee paid out to workers per successful crank, in the mint's base units.
+    pub crank_fee: u64,
+    /// The address of the mint.
+    pub mint: Pubkey,
+}
+
 /// Trait for reading and writing to the config account.
 pub trait ConfigAccount {
     /// Initialize the account to hold config object.
     fn init(&mut self, admin: Pubkey, worker_pool: Pubkey) -> Result<()>;
 
+    /// Set defaults for the fields appended to the config object since it was initialized.
+    fn migrate(&mut self) -> Result<()>;
+
     /// Updates the config object.
     fn update(&mut self, settings: ConfigSettings) -> Result<()>;
 }
@@ -60,14 +90,29 @@ impl ConfigAccount for Account<'_, Config> {
     fn init(&mut self, admin: Pubkey, worker_pool: Pubkey) -> Result<()> {
         self.admin = admin;
         self.crank_fee = DEFAULT_CRANK_FEE;
+        self.fee_mint = None;
+        self.max_lamports_per_signature = DEFAULT_MAX_LAMPORTS_PER_SIGNATURE;
+        self.max_rate_limit = DEFAULT_MAX_RATE_LIMIT;
         self.worker_pool = worker_pool;
         Ok(())
     }
 
+    fn migrate(&mut self) -> Result<()> {
+        if self.max_lamports_per_signature == 0 {
+            self.max_lamports_per_signature = DEFAULT_MAX_LAMPORTS_PER_SIGNATURE;
+        }
+        if self.max_rate_limit == 0 {
+            self.max_rate_limit = DEFAULT_MAX_RATE_LIMIT;
+        }
+        Ok(())
+    }
+
     fn update(&mut self, settings: ConfigSettings) -> Result<()> {
         self.admin = settings.admin;
         self.crank_fee = settings.crank_fee;
-        self.worker_pool;
+        self.fee_mint = settings.fee_mint;
+        self.max_lamports_per_signature = settings.max_lamports_per_signature;
+        self.max_rate_limit = settings.max_rate_limit;
         Ok(())
     }
 }
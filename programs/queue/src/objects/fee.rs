@@ -1,5 +1,6 @@
 use {
-    super::Queue,
+    super::{Queue, QueueAccount},
+    crate::errors::ClockworkError,
     anchor_lang::{prelude::*, AnchorDeserialize},
     std::convert::TryFrom,
 };
@@ -66,6 +67,11 @@ impl FeeAccount for Account<'_, Fee> {
     }
 
     fn claim_balance(&mut self, amount: u64, pay_to: &mut SystemAccount) -> Result<()> {
+        require!(
+            amount <= self.balance,
+            ClockworkError::InsufficientFeeBalance
+        );
+
         // Withdraw from the worker amount
         self.balance = self.balance.checked_sub(amount).unwrap();
 
@@ -85,6 +91,11 @@ impl FeeAccount for Account<'_, Fee> {
     }
 
     fn claim_withholding(&mut self, amount: u64, pay_to: &mut SystemAccount) -> Result<()> {
+        require!(
+            amount <= self.withholding,
+            ClockworkError::InsufficientFeeBalance
+        );
+
         // Withdraw from the admin balance
         self.withholding = self.withholding.checked_sub(amount).unwrap();
 
@@ -108,11 +119,7 @@ impl FeeAccount for Account<'_, Fee> {
         self.withholding = self.withholding.checked_add(amount).unwrap();
 
         // Transfer lamports
-        **queue.to_account_info().try_borrow_mut_lamports()? = queue
-            .to_account_info()
-            .lamports()
-            .checked_sub(amount)
-            .unwrap();
+        queue.debit(amount)?;
         **self.to_account_info().try_borrow_mut_lamports()? = self
             .to_account_info()
             .lamports()
@@ -127,11 +134,7 @@ impl FeeAccount for Account<'_, Fee> {
         self.balance = self.balance.checked_add(amount).unwrap();
 
         // Transfer lamports
-        **queue.to_account_info().try_borrow_mut_lamports()? = queue
-            .to_account_info()
-            .lamports()
-            .checked_sub(amount)
-            .unwrap();
+        queue.debit(amount)?;
         **self.to_account_info().try_borrow_mut_lamports()? = self
             .to_account_info()
             .lamports()
@@ -0,0 +1,169 @@
+use {
+    crate::errors::ClockworkError,
+    anchor_lang::{prelude::*, AnchorDeserialize},
+    std::convert::TryFrom,
+};
+
+pub const SEED_FUNDING: &[u8] = b"funding";
+
+/// Records the sponsors who have funded a queue and how much each has contributed. The account is derived from the
+/// queue's creation slot too, so a queue recreated at the same address starts with a new funding account.
+#[account]
+#[derive(Debug)]
+pub struct Funding {
+    /// Whether or not the queue has been deleted. Sponsors may only reclaim their shares once it has.
+    pub is_closed: bool,
+    /// The queue being funded.
+    pub queue: Pubkey,
+    /// The slot at which the queue being funded was created.
+    pub queue_created_at: u64,
+    /// The number of lamports held for the sponsors to reclaim.
+    pub reclaimable: u64,
+    /// The sponsors of the queue and their contributions.
+    pub sponsors: Vec<Sponsor>,
+    /// The total number of lamports contributed by the sponsors who have not yet reclaimed their shares.
+    pub total_contributed: u64,
+}
+
+impl Funding {
+    /// Derive the pubkey of a funding account.
+    pub fn pubkey(queue: Pubkey, queue_created_at: u64) -> Pubkey {
+        Pubkey::find_program_address(
+            &[
+                SEED_FUNDING,
+                queue.as_ref(),
+                queue_created_at.to_le_bytes().as_ref(),
+            ],
+            &crate::ID,
+        )
+        .0
+    }
+}
+
+impl TryFrom<Vec<u8>> for Funding {
+    type Error = Error;
+    fn try_from(data: Vec<u8>) -> std::result::Result<Self, Self::Error> {
+        Funding::try_deserialize(&mut data.as_slice())
+    }
+}
+
+/// A sponsor of a queue and the lamports they have contributed to it.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, PartialEq, Eq)]
+pub struct Sponsor {
+    /// The number of lamports contributed.
+    pub amount: u64,
+    /// The sponsor's address.
+    pub sponsor: Pubkey,
+}
+
+/// A sponsor's pro-rata share of the reclaimable lamports, given their contribution and the total contributed by
+/// the sponsors who have not yet reclaimed, rounding down.
+fn reclaim_share(contribution: u64, reclaimable: u64, total_contributed: u64) -> u64 {
+    (reclaimable as u128)
+        .checked_mul(contribution as u128)
+        .unwrap()
+        .checked_div(total_contributed as u128)
+        .unwrap_or(0) as u64
+}
+
+/// Trait for reading and writing to a funding account.
+pub trait FundingAccount {
+    /// Get the pubkey of the funding account.
+    fn pubkey(&self) -> Pubkey;
+
+    /// Initialize the account to hold funding object.
+    fn init(&mut self, queue: Pubkey, queue_created_at: u64) -> Result<()>;
+
+    /// Record a sponsor's contribution to the queue.
+    fn contribute(&mut self, amount: u64, sponsor: Pubkey) -> Result<()>;
+
+    /// Remove a sponsor and pay their pro-rata share of the reclaimable lamports to the given account.
+    /// Returns the number of lamports paid.
+    fn reclaim(&mut self, pay_to: &AccountInfo, sponsor: Pubkey) -> Result<u64>;
+
+    /// Reallocate the memory allocation for the account.
+    fn realloc(&mut self) -> Result<()>;
+}
+
+impl FundingAccount for Account<'_, Funding> {
+    fn pubkey(&self) -> Pubkey {
+        Funding::pubkey(self.queue, self.queue_created_at)
+    }
+
+    fn init(&mut self, queue: Pubkey, queue_created_at: u64) -> Result<()> {
+        self.is_closed = false;
+        self.queue = queue;
+        self.queue_created_at = queue_created_at;
+        self.reclaimable = 0;
+        self.sponsors = vec![];
+        self.total_contributed = 0;
+        Ok(())
+    }
+
+    fn contribute(&mut self, amount: u64, sponsor: Pubkey) -> Result<()> {
+        // Verify the queue has not been deleted.
+        require!(!self.is_closed, ClockworkError::InvalidFundingState);
+
+        // Add the amount to the sponsor's contribution.
+        match self.sponsors.iter_mut().find(|s| s.sponsor.eq(&sponsor)) {
+            None => self.sponsors.push(Sponsor { amount, sponsor }),
+            Some(s) => s.amount = s.amount.checked_add(amount).unwrap(),
+        }
+        self.total_contributed = self.total_contributed.checked_add(amount).unwrap();
+        Ok(())
+    }
+
+    fn reclaim(&mut self, pay_to: &AccountInfo, sponsor: Pubkey) -> Result<u64> {
+        // Verify the queue has been deleted.
+        require!(self.is_closed, ClockworkError::InvalidFundingState);
+
+        // Remove the sponsor.
+        let index = self
+            .sponsors
+            .iter()
+            .position(|s| s.sponsor.eq(&sponsor))
+            .ok_or(ClockworkError::SponsorNotFound)?;
+        let contribution = self.sponsors.remove(index).amount;
+
+        // Compute the sponsor's pro-rata share of the reclaimable lamports.
+        let share = reclaim_share(contribution, self.reclaimable, self.total_contributed);
+        self.reclaimable = self.reclaimable.checked_sub(share).unwrap();
+        self.total_contributed = self.total_contributed.checked_sub(contribution).unwrap();
+
+        // Transfer lamports to the pay_to account
+        **self.to_account_info().try_borrow_mut_lamports()? = self
+            .to_account_info()
+            .lamports()
+            .checked_sub(share)
+            .unwrap();
+        **pay_to.try_borrow_mut_lamports()? = pay_to.lamports().checked_add(share).unwrap();
+
+        Ok(share)
+    }
+
+    fn realloc(&mut self) -> Result<()> {
+        // Realloc memory for the funding account
+        let data_len = 8 + self.try_to_vec()?.len();
+        self.to_account_info().realloc(data_len, false)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_reclaim_share() {
+        assert_eq!(reclaim_share(100, 100, 300), 33);
+        assert_eq!(reclaim_share(200, 100, 300), 66);
+        assert_eq!(reclaim_share(300, 100, 300), 100);
+        assert_eq!(reclaim_share(100, 0, 300), 0);
+
+        // Large balances do not overflow.
+        assert_eq!(reclaim_share(u64::MAX, u64::MAX, u64::MAX), u64::MAX);
+
+        // Nothing is owed if nothing was contributed.
+        assert_eq!(reclaim_share(0, 100, 0), 0);
+    }
+}
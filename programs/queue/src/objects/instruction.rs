@@ -63,6 +63,27 @@ impl TryFrom<Vec<u8>> for InstructionData {
     }
 }
 
+/// An instruction to run on behalf of a queue.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug, Hash, PartialEq)]
+pub enum QueueInstruction {
+    /// An instruction loaded from the given instruction buffer account at crank time.
+    Buffered { buffer: Pubkey },
+    /// An instruction stored inline in the queue.
+    Inline(InstructionData),
+}
+
+impl From<Instruction> for QueueInstruction {
+    fn from(instruction: Instruction) -> Self {
+        QueueInstruction::Inline(instruction.into())
+    }
+}
+
+impl From<InstructionData> for QueueInstruction {
+    fn from(instruction: InstructionData) -> Self {
+        QueueInstruction::Inline(instruction)
+    }
+}
+
 /// Account metadata needed to execute an instruction on Solana.
 #[derive(AnchorDeserialize, AnchorSerialize, BorshSchema, Clone, Debug, Hash, PartialEq)]
 pub struct AccountMetaData {
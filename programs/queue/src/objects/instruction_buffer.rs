@@ -0,0 +1,102 @@
+use {
+    super::InstructionData,
+    crate::errors::ClockworkError,
+    anchor_lang::{prelude::*, AnchorDeserialize},
+    std::{convert::TryFrom, mem::size_of},
+};
+
+pub const SEED_INSTRUCTION_BUFFER: &[u8] = b"instruction_buffer";
+
+/// Holds a serialized instruction too large to store inline in a queue. Buffers are written in chunks and
+/// referenced from a queue's instructions by pubkey. Only the header below is deserialized; the serialized
+/// instruction is stored raw in the account data after it, and is read and written in place.
+#[account]
+#[derive(Debug)]
+pub struct InstructionBuffer {
+    /// The owner of this buffer. Only queues with the same authority may load instructions from it.
+    pub authority: Pubkey,
+    /// The id of the buffer, given by the authority.
+    pub id: String,
+    /// The number of bytes reserved for the serialized instruction.
+    pub size: u64,
+}
+
+impl InstructionBuffer {
+    /// Derive the pubkey of an instruction buffer account.
+    pub fn pubkey(authority: Pubkey, id: String) -> Pubkey {
+        Pubkey::find_program_address(
+            &[SEED_INSTRUCTION_BUFFER, authority.as_ref(), id.as_bytes()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// The number of bytes of account data needed for a buffer with the given id and size.
+    pub fn space(id: &str, size: u64) -> usize {
+        8 + size_of::<Pubkey>() + 4 + id.len() + size_of::<u64>() + size as usize
+    }
+
+    /// Parse the header of an instruction buffer and the instruction held in it from the buffer's account data.
+    /// Any bytes after the instruction are ignored.
+    pub fn try_parse(data: &[u8]) -> Result<(Self, InstructionData)> {
+        let header = InstructionBuffer::try_deserialize(&mut &data[..])?;
+        let instruction = data
+            .get(header.payload_offset()..)
+            .and_then(|mut payload| InstructionData::deserialize(&mut payload).ok())
+            .ok_or(ClockworkError::InvalidInstructionBuffer)?;
+        Ok((header, instruction))
+    }
+
+    /// The byte offset in the account data at which the serialized instruction starts.
+    fn payload_offset(&self) -> usize {
+        InstructionBuffer::space(&self.id, 0)
+    }
+}
+
+impl TryFrom<Vec<u8>> for InstructionBuffer {
+    type Error = Error;
+    fn try_from(data: Vec<u8>) -> std::result::Result<Self, Self::Error> {
+        InstructionBuffer::try_deserialize(&mut data.as_slice())
+    }
+}
+
+/// Trait for reading and writing to an instruction buffer account.
+pub trait InstructionBufferAccount {
+    /// Get the pubkey of the instruction buffer account.
+    fn pubkey(&self) -> Pubkey;
+
+    /// Initialize the account to hold an instruction buffer object of the given size.
+    fn init(&mut self, authority: Pubkey, id: String, size: u64) -> Result<()>;
+
+    /// Write a chunk of bytes into the buffer at the given offset.
+    fn write(&mut self, data: Vec<u8>, offset: u64) -> Result<()>;
+}
+
+impl InstructionBufferAccount for Account<'_, InstructionBuffer> {
+    fn pubkey(&self) -> Pubkey {
+        InstructionBuffer::pubkey(self.authority, self.id.clone())
+    }
+
+    fn init(&mut self, authority: Pubkey, id: String, size: u64) -> Result<()> {
+        self.authority = authority;
+        self.id = id;
+        self.size = size;
+        Ok(())
+    }
+
+    fn write(&mut self, data: Vec<u8>, offset: u64) -> Result<()> {
+        let start = offset as usize;
+        let end = start.saturating_add(data.len());
+        require!(
+            end.le(&(self.size as usize)),
+            ClockworkError::InstructionBufferTooSmall
+        );
+        let payload_offset = self.payload_offset();
+        self.to_account_info()
+            .try_borrow_mut_data()?
+            .get_mut(payload_offset.saturating_add(start)..payload_offset.saturating_add(end))
+            .ok_or(ClockworkError::InstructionBufferTooSmall)?
+            .copy_from_slice(&data);
+        Ok(())
+    }
+}
@@ -0,0 +1,345 @@
+use {
+    super::{
+        ClockData, ExecContext, InstructionData, Queue, Trigger, TriggerContext,
+        DEFAULT_MAX_RETRIES,
+    },
+    crate::{errors::ClockworkError, utils::account_data_hash},
+    anchor_lang::{prelude::*, AnchorDeserialize, Discriminator},
+};
+
+/// A queue in the layout it was originally initialized with, before its fields were reordered and appended to. Legacy
+/// queues share the discriminator of the current `Queue`, and can be converted to it with `queue_migrate`.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub struct LegacyQueue {
+    /// The owner of this queue.
+    pub authority: Pubkey,
+    /// The cluster clock at the moment the queue was created.
+    pub created_at: ClockData,
+    /// The context of the current thread execution state.
+    pub exec_context: Option<LegacyExecContext>,
+    /// The id of the queue, given by the authority.
+    pub id: String,
+    /// The instruction to kick-off the thread.
+    pub kickoff_instruction: InstructionData,
+    /// The next instruction in the thread.
+    pub next_instruction: Option<InstructionData>,
+    /// Whether or not the queue is currently paused.
+    pub paused: bool,
+    /// The maximum number of cranks allowed per slot.
+    pub rate_limit: u64,
+    /// The triggering event to kickoff a thread.
+    pub trigger: LegacyTrigger,
+}
+
+impl LegacyQueue {
+    /// Parse a legacy queue from the queue's account data. Fails if the account already has the current layout.
+    pub fn try_parse(data: &[u8]) -> Result<Self> {
+        require!(
+            Queue::try_deserialize(&mut &data[..]).is_err(),
+            ClockworkError::InvalidLegacyQueue
+        );
+        require!(
+            data.starts_with(&Queue::discriminator()),
+            ClockworkError::InvalidLegacyQueue
+        );
+        LegacyQueue::deserialize(&mut &data[8..])
+            .map_err(|_err| ClockworkError::InvalidLegacyQueue.into())
+    }
+
+    /// The account watched by the queue's trigger, if it has an account trigger.
+    pub fn watched_account(&self) -> Option<Pubkey> {
+        match self.trigger {
+            LegacyTrigger::Account { pubkey } => Some(pubkey),
+            _ => None,
+        }
+    }
+
+    /// Convert the legacy queue to the current layout, setting the fields appended since to the defaults of a new
+    /// queue. Account trigger contexts were hashed with a running hash that can no longer be reproduced, so they are
+    /// rehashed from the watched account's current data, which must be the first of the given accounts.
+    pub fn migrate(self, account_infos: &[AccountInfo]) -> Result<Queue> {
+        let exec_context = match self.exec_context {
+            None => None,
+            Some(exec_context) => {
+                let trigger_context = match exec_context.trigger_context {
+                    LegacyTriggerContext::Account { .. } => {
+                        let pubkey = self
+                            .watched_account()
+                            .ok_or(ClockworkError::InvalidQueueState)?;
+                        let account_info = account_infos
+                            .first()
+                            .ok_or(ClockworkError::InvalidTrigger)?;
+                        require!(pubkey.eq(account_info.key), ClockworkError::InvalidTrigger);
+                        TriggerContext::Account {
+                            data_hash: account_data_hash(
+                                &account_info.try_borrow_data()?,
+                                None,
+                                None,
+                            ),
+                        }
+                    }
+                    LegacyTriggerContext::Cron { started_at } => {
+                        TriggerContext::Cron { started_at }
+                    }
+                    LegacyTriggerContext::Immediate => TriggerContext::Immediate,
+                };
+                Some(ExecContext {
+                    cranks_since_slot: exec_context.cranks_since_slot,
+                    last_crank_at: exec_context.last_crank_at,
+                    trigger_context: Some(trigger_context),
+                    window_exec_count: 0,
+                    window_started_at: 0,
+                })
+            }
+        };
+
+        Ok(Queue {
+            address_lookup_tables: vec![],
+            authority: self.authority,
+            auto_close: false,
+            completion_count: 0,
+            compute_unit_limit: None,
+            compute_unit_price: None,
+            consecutive_failures: 0,
+            created_at: self.created_at,
+            data_placeholders: vec![],
+            error_instruction: None,
+            exec_context,
+            exec_count: 0,
+            exec_history: vec![],
+            exec_history_capacity: 0,
+            exec_rate_limit: None,
+            expires_at: None,
+            fee_bid: None,
+            fee_mint: None,
+            id: self.id,
+            kickoff_instructions: vec![self.kickoff_instruction.into()],
+            last_failure_at: None,
+            low_balance_threshold: None,
+            max_executions: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            next_instructions: self.next_instruction.into_iter().map(Into::into).collect(),
+            paused: self.paused,
+            pool: None,
+            rate_limit: self.rate_limit,
+            sponsored_lamports: 0,
+            trigger: match self.trigger {
+                LegacyTrigger::Account { pubkey } => Trigger::Account {
+                    pubkey,
+                    offset: None,
+                    size: None,
+                },
+                LegacyTrigger::Cron {
+                    schedule,
+                    skippable,
+                } => Trigger::Cron {
+                    schedule,
+                    skippable,
+                    timezone: None,
+                },
+                LegacyTrigger::Immediate => Trigger::Immediate,
+            },
+        })
+    }
+}
+
+/// The original triggering conditions of a queue.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+pub enum LegacyTrigger {
+    /// Crank whenever the data of an account changes.
+    Account {
+        /// The address of the account to subscribe to.
+        pubkey: Pubkey,
+    },
+
+    /// Crank according to a one-time or recurring schedule.
+    Cron {
+        /// The schedule in cron syntax.
+        schedule: String,
+
+        /// Whether missed triggering moments may be skipped.
+        skippable: bool,
+    },
+
+    /// Crank as soon as the queue is created.
+    Immediate,
+}
+
+/// The original execution context of a transaction thread.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub struct LegacyExecContext {
+    /// Number of cranks since the last tx reimbursement.
+    pub cranks_since_reimbursement: u64,
+
+    /// Number of cranks in this slot.
+    pub cranks_since_slot: u64,
+
+    /// Slot of the last crank
+    pub last_crank_at: u64,
+
+    /// Context for the triggering condition
+    pub trigger_context: LegacyTriggerContext,
+}
+
+/// The original context of the event which triggered a transaction thread.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug)]
+pub enum LegacyTriggerContext {
+    /// A running hash of the observed account data.
+    Account {
+        /// The account's data hash.
+        data_hash: u64,
+    },
+
+    /// A cron execution context.
+    Cron {
+        /// The threshold moment the schedule was waiting for.
+        started_at: i64,
+    },
+
+    /// The immediate trigger context.
+    Immediate,
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::objects::{AccountMetaData, QueueInstruction},
+        anchor_lang::solana_program::system_program,
+    };
+
+    fn legacy_queue(trigger: LegacyTrigger, trigger_context: LegacyTriggerContext) -> LegacyQueue {
+        LegacyQueue {
+            authority: Pubkey::new_unique(),
+            created_at: ClockData::from(Clock::default()),
+            exec_context: Some(LegacyExecContext {
+                cranks_since_reimbursement: 3,
+                cranks_since_slot: 2,
+                last_crank_at: 100,
+                trigger_context,
+            }),
+            id: "legacy".into(),
+            kickoff_instruction: InstructionData {
+                program_id: Pubkey::new_unique(),
+                accounts: vec![AccountMetaData {
+                    pubkey: Pubkey::new_unique(),
+                    is_signer: false,
+                    is_writable: true,
+                }],
+                data: vec![1, 2, 3],
+            },
+            next_instruction: None,
+            paused: true,
+            rate_limit: 5,
+            trigger,
+        }
+    }
+
+    fn legacy_queue_data(legacy_queue: &LegacyQueue) -> Vec<u8> {
+        let mut data = Queue::discriminator().to_vec();
+        legacy_queue.serialize(&mut data).unwrap();
+        data
+    }
+
+    #[test]
+    fn test_migrate_account_trigger() {
+        let watched_pubkey = Pubkey::new_unique();
+        let mut watched_data = vec![7; 64];
+        let mut watched_lamports = 0;
+        let watched_info = AccountInfo::new(
+            &watched_pubkey,
+            false,
+            false,
+            &mut watched_lamports,
+            &mut watched_data,
+            &system_program::ID,
+            false,
+            0,
+        );
+        let legacy_queue = legacy_queue(
+            LegacyTrigger::Account {
+                pubkey: watched_pubkey,
+            },
+            LegacyTriggerContext::Account { data_hash: 42 },
+        );
+
+        // Parse the original layout, and migrate it against the watched account.
+        let data = legacy_queue_data(&legacy_queue);
+        assert!(Queue::try_deserialize(&mut data.as_slice()).is_err());
+        let parsed = LegacyQueue::try_parse(&data).unwrap();
+        assert_eq!(parsed.watched_account(), Some(watched_pubkey));
+        assert!(parsed.clone().migrate(&[]).is_err());
+        let queue = parsed.migrate(std::slice::from_ref(&watched_info)).unwrap();
+
+        // The migrated queue keeps its original state, and its trigger context is rehashed with the current hash.
+        assert_eq!(queue.authority, legacy_queue.authority);
+        assert_eq!(queue.id, legacy_queue.id);
+        assert_eq!(
+            queue.kickoff_instructions,
+            vec![QueueInstruction::Inline(legacy_queue.kickoff_instruction)]
+        );
+        assert!(queue.next_instructions.is_empty());
+        assert!(queue.paused);
+        assert_eq!(queue.rate_limit, 5);
+        assert!(matches!(
+            queue.trigger,
+            Trigger::Account {
+                pubkey,
+                offset: None,
+                size: None,
+            } if pubkey.eq(&watched_pubkey)
+        ));
+        assert_eq!(
+            queue.exec_context,
+            Some(ExecContext {
+                cranks_since_slot: 2,
+                last_crank_at: 100,
+                trigger_context: Some(TriggerContext::Account {
+                    data_hash: account_data_hash(&[7; 64], None, None),
+                }),
+                window_exec_count: 0,
+                window_started_at: 0,
+            })
+        );
+
+        // The migrated queue deserializes with the current layout, and can't be migrated again.
+        let mut data = vec![];
+        queue.try_serialize(&mut data).unwrap();
+        let queue = Queue::try_deserialize(&mut data.as_slice()).unwrap();
+        assert_eq!(queue.authority, legacy_queue.authority);
+        assert!(LegacyQueue::try_parse(&data).is_err());
+    }
+
+    #[test]
+    fn test_migrate_cron_trigger() {
+        let mut legacy_queue = legacy_queue(
+            LegacyTrigger::Cron {
+                schedule: "0 * * * * * *".into(),
+                skippable: true,
+            },
+            LegacyTriggerContext::Cron { started_at: 1_000 },
+        );
+        legacy_queue.next_instruction = Some(legacy_queue.kickoff_instruction.clone());
+
+        let queue = LegacyQueue::try_parse(&legacy_queue_data(&legacy_queue))
+            .unwrap()
+            .migrate(&[])
+            .unwrap();
+        assert_eq!(
+            queue.next_instructions,
+            vec![QueueInstruction::Inline(legacy_queue.kickoff_instruction)]
+        );
+        assert!(matches!(
+            queue.trigger,
+            Trigger::Cron {
+                skippable: true,
+                timezone: None,
+                ..
+            }
+        ));
+        assert_eq!(
+            queue.trigger_context(),
+            Some(TriggerContext::Cron { started_at: 1_000 })
+        );
+    }
+}
@@ -3,11 +3,17 @@
 mod clock;
 mod config;
 mod fee;
+mod funding;
 mod instruction;
+mod instruction_buffer;
+mod legacy_queue;
 mod queue;
 
 pub use clock::*;
 pub use config::*;
 pub use fee::*;
+pub use funding::*;
 pub use instruction::*;
+pub use instruction_buffer::*;
+pub use legacy_queue::*;
 pub use queue::*;
@@ -1,44 +1,122 @@
 use {
-    super::{ClockData, InstructionData},
-    crate::errors::ClockworkError,
+    super::{ClockData, Funding, InstructionBuffer, InstructionData, QueueInstruction},
+    crate::{
+        errors::ClockworkError,
+        utils::{account_data_hash, next_cron_timestamp, prev_cron_timestamp, CronTimezone},
+    },
     anchor_lang::{
         prelude::*,
         solana_program::{
             instruction::Instruction,
             program::{get_return_data, invoke_signed},
         },
-        AnchorDeserialize, AnchorSerialize,
+        AccountsClose, AnchorDeserialize, AnchorSerialize,
+    },
+    anchor_spl::{
+        associated_token::get_associated_token_address,
+        token::{self, close_account, transfer, CloseAccount, TokenAccount, Transfer},
     },
+    clockwork_pool_program::objects::Pool,
     std::{
         convert::TryFrom,
         hash::{Hash, Hasher},
+        io::Write,
+        mem::size_of,
+        str::FromStr,
     },
 };
 
 pub const SEED_QUEUE: &[u8] = b"queue";
 
-const DEFAULT_RATE_LIMIT: u64 = 10;
+pub const DEFAULT_MAX_RETRIES: u64 = 3;
+
+pub const DEFAULT_RATE_LIMIT: u64 = 10;
+
+/// The maximum number of slots to wait between retries of a failing queue.
+const MAX_RETRY_BACKOFF: u64 = 1024;
+
+/// The maximum age, in slots, of a crank attempt whose failure may be reported. This is the lifetime of a recent
+/// blockhash, after which the attempt could no longer have landed.
+const MAX_FAILED_ATTEMPT_AGE: u64 = 150;
+
+/// The maximum number of execution records a queue may retain.
+pub const MAX_EXEC_HISTORY_CAPACITY: u64 = 32;
+
+/// The maximum number of accounts a trigger may watch.
+pub const MAX_TRIGGER_ACCOUNTS: usize = 8;
+
+/// The number of compute units the runtime grants each instruction of a transaction which does not request a limit.
+pub const DEFAULT_INSTRUCTION_COMPUTE_UNIT_LIMIT: u32 = 200_000;
+
+/// The maximum number of compute units a transaction may request.
+pub const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
 
 /// Tracks the current state of a transaction thread on Solana.
 #[account]
 #[derive(Debug)]
 pub struct Queue {
+    /// The address lookup tables workers may use to load the accounts of the queue's cranks.
+    pub address_lookup_tables: Vec<Pubkey>,
     /// The owner of this queue.
     pub authority: Pubkey,
+    /// Whether or not the queue should be closed, returning its lamports to the authority, once it has expired.
+    pub auto_close: bool,
+    /// The number of thread executions that have completed.
+    pub completion_count: u64,
+    /// The number of compute units to request for each crank transaction. If none, the runtime's default limit applies.
+    pub compute_unit_limit: Option<u32>,
+    /// The price, in micro-lamports per compute unit, to pay as a priority fee for each crank transaction.
+    pub compute_unit_price: Option<u64>,
+    /// The number of cranks that have failed since the last successful crank.
+    pub consecutive_failures: u64,
     /// The cluster clock at the moment the queue was created.
     pub created_at: ClockData,
+    /// The placeholders to fill in the data of the queue's instructions at crank time.
+    pub data_placeholders: Vec<DataPlaceholder>,
+    /// The instruction to run as the next crank once the queue has exhausted its retries. If none, or if the error
+    /// instruction itself fails, the queue is paused instead.
+    pub error_instruction: Option<QueueInstruction>,
     /// The context of the current thread execution state.
     pub exec_context: Option<ExecContext>,
+    /// The number of thread executions that have been started.
+    pub exec_count: u64,
+    /// The most recent thread executions, ordered from oldest to newest.
+    pub exec_history: Vec<ExecRecord>,
+    /// The maximum number of execution records to retain.
+    pub exec_history_capacity: u64,
+    /// The maximum number of thread executions which may be started within a window of time.
+    pub exec_rate_limit: Option<ExecRateLimit>,
+    /// The unix timestamp after which no new thread executions may be started.
+    pub expires_at: Option<i64>,
+    /// The fee the queue bids to pay per crank. If none, the config's crank fee is paid.
+    pub fee_bid: Option<u64>,
+    /// The SPL token mint the queue pays crank fees in, from its associated token account. If none, or if the mint
+    /// is no longer whitelisted by the config, crank fees are paid in lamports.
+    pub fee_mint: Option<Pubkey>,
     /// The id of the queue, given by the authority.
     pub id: String,
-    /// The instruction to kick-off the thread.
-    pub kickoff_instruction: InstructionData,
-    /// The next instruction in the thread.
-    pub next_instruction: Option<InstructionData>,
+    /// The ordered list of instructions to kick-off the thread.
+    pub kickoff_instructions: Vec<QueueInstruction>,
+    /// The slot of the most recently reported crank failure.
+    pub last_failure_at: Option<u64>,
+    /// The number of spendable lamports, above the rent-exempt minimum, below which the queue is paused.
+    pub low_balance_threshold: Option<u64>,
+    /// The maximum number of thread executions that may be started.
+    pub max_executions: Option<u64>,
+    /// The number of times a failing crank may be retried before the queue is paused or its error instruction is run.
+    pub max_retries: u64,
+    /// The ordered list of instructions to execute in the next crank of the thread.
+    pub next_instructions: Vec<QueueInstruction>,
     /// Whether or not the queue is currently paused.
     pub paused: bool,
+    /// The worker pool allowed to crank the queue. If none, the config's worker pool is used.
+    pub pool: Option<Pubkey>,
     /// The maximum number of cranks allowed per slot.
     pub rate_limit: u64,
+    /// The lamports contributed by sponsors. Up to this many spendable lamports are held back for the sponsors to
+    /// reclaim when the queue is deleted. Sponsored lamports are spent last, so this is drawn down whenever the
+    /// queue's spendable balance falls below it. Updates which grow the queue pay its added rent on top of them.
+    pub sponsored_lamports: u64,
     /// The triggering event to kickoff a thread.
     pub trigger: Trigger,
 }
@@ -48,6 +126,271 @@ impl Queue {
     pub fn pubkey(authority: Pubkey, id: String) -> Pubkey {
         Pubkey::find_program_address(&[SEED_QUEUE, authority.as_ref(), id.as_bytes()], &crate::ID).0
     }
+
+    /// The fee to pay per crank: the queue's bid, but never less than the given minimum crank fee.
+    pub fn crank_fee(&self, min_crank_fee: u64) -> u64 {
+        self.fee_bid.unwrap_or(min_crank_fee).max(min_crank_fee)
+    }
+
+    /// Whether the queue has reached its maximum number of executions or its expiry.
+    pub fn is_expired(&self, unix_timestamp: i64) -> bool {
+        matches!(self.max_executions, Some(max_executions) if self.exec_count >= max_executions)
+            || matches!(self.expires_at, Some(expires_at) if unix_timestamp >= expires_at)
+    }
+
+    /// Record a failed crank. Once the queue has exhausted its retries, its error instruction is scheduled as the
+    /// next crank of its thread, starting the given exec context if the failed crank would have started a new thread.
+    /// The error instruction runs in its own crank so that its failure can be recorded too, which pauses the queue
+    /// just as it does for queues without an error instruction.
+    pub fn fail(&mut self, current_slot: u64, exec_context: Option<ExecContext>) {
+        // Record the failure
+        self.consecutive_failures = self.consecutive_failures.checked_add(1).unwrap();
+        self.last_failure_at = Some(current_slot);
+
+        // Exit early if the queue still has retries remaining
+        if self.consecutive_failures <= self.max_retries {
+            return;
+        }
+
+        // The queue is paused if it has no error instruction, or if its error instruction is the crank which failed.
+        let error_instruction = self.error_instruction.clone().filter(|error_instruction| {
+            self.next_instructions
+                .ne(std::slice::from_ref(error_instruction))
+        });
+        match error_instruction {
+            None => {
+                // Pause the queue
+                self.paused = true;
+            }
+            Some(error_instruction) => {
+                // Abandon the current thread and run the error instruction next
+                if exec_context.is_some() {
+                    self.exec_context = exec_context;
+                }
+                self.next_instructions = vec![error_instruction];
+            }
+        }
+    }
+
+    /// The slot at which a failing queue may be retried. Backoff doubles with each consecutive failure.
+    pub fn retry_at(&self) -> Option<u64> {
+        self.last_failure_at.map(|last_failure_at| {
+            let backoff = 2u64
+                .checked_pow(self.consecutive_failures as u32)
+                .unwrap_or(MAX_RETRY_BACKOFF)
+                .min(MAX_RETRY_BACKOFF);
+            last_failure_at.saturating_add(backoff)
+        })
+    }
+
+    /// Verify a worker's report that a crank attempted at the given slot failed. The attempt must have been made
+    /// within the lifetime of a recent blockhash and once the queue's retry backoff had elapsed, and no crank may have
+    /// succeeded since.
+    pub fn verify_failed_attempt(&self, attempted_at: u64, current_slot: u64) -> Result<()> {
+        require!(
+            attempted_at <= current_slot
+                && current_slot.saturating_sub(attempted_at) <= MAX_FAILED_ATTEMPT_AGE,
+            ClockworkError::InvalidFailedAttempt
+        );
+        if let Some(retry_at) = self.retry_at() {
+            require!(attempted_at >= retry_at, ClockworkError::InvalidRetry);
+        }
+        if let Some(exec_context) = self.exec_context {
+            require!(
+                attempted_at >= exec_context.last_crank_at,
+                ClockworkError::InvalidFailedAttempt
+            );
+        }
+        Ok(())
+    }
+
+    /// The number of bytes to reserve for execution records which have not been written yet.
+    pub fn exec_history_reserved_len(&self) -> usize {
+        (self.exec_history_capacity as usize)
+            .saturating_sub(self.exec_history.len())
+            .checked_mul(size_of::<ExecRecord>())
+            .unwrap()
+    }
+
+    /// Invoke an instruction on behalf of the queue, delegating the Clockwork payer to the worker.
+    pub fn invoke(
+        &self,
+        instruction: &InstructionData,
+        account_infos: &[AccountInfo],
+        bump: u8,
+        worker: &Signer,
+    ) -> Result<()> {
+        self.invoke_with_seeds(
+            instruction,
+            account_infos,
+            &[&[
+                SEED_QUEUE,
+                self.authority.as_ref(),
+                self.id.as_bytes(),
+                &[bump],
+            ]],
+            worker,
+        )
+    }
+
+    /// Invoke an instruction without the queue's signature, delegating the Clockwork payer to the worker.
+    pub fn invoke_unsigned(
+        &self,
+        instruction: &InstructionData,
+        account_infos: &[AccountInfo],
+        worker: &Signer,
+    ) -> Result<()> {
+        self.invoke_with_seeds(instruction, account_infos, &[], worker)
+    }
+
+    fn invoke_with_seeds(
+        &self,
+        instruction: &InstructionData,
+        account_infos: &[AccountInfo],
+        signers_seeds: &[&[&[u8]]],
+        worker: &Signer,
+    ) -> Result<()> {
+        // Inject the worker's pubkey for the Clockwork payer ID
+        let normalized_accounts: &mut Vec<AccountMeta> = &mut vec![];
+        instruction.accounts.iter().for_each(|acc| {
+            let acc_pubkey = if acc.pubkey == crate::utils::PAYER_PUBKEY {
+                worker.key()
+            } else {
+                acc.pubkey
+            };
+            normalized_accounts.push(AccountMeta {
+                pubkey: acc_pubkey,
+                is_signer: acc.is_signer,
+                is_writable: acc.is_writable,
+            });
+        });
+
+        // Fill the placeholders in the instruction data
+        let mut data = instruction.data.clone();
+        for placeholder in self.data_placeholders.iter() {
+            if placeholder.program_id.ne(&instruction.program_id) {
+                continue;
+            }
+            let offset = placeholder.offset as usize;
+            data.get_mut(offset..offset.saturating_add(8))
+                .ok_or(ClockworkError::InvalidPlaceholder)?
+                .copy_from_slice(&self.placeholder_value(placeholder.value)?);
+        }
+
+        // Invoke the provided instruction
+        invoke_signed(
+            &Instruction {
+                program_id: instruction.program_id,
+                data,
+                accounts: normalized_accounts.to_vec(),
+            },
+            account_infos,
+            signers_seeds,
+        )?;
+
+        // Verify that the inner ix did not write data to the worker address
+        require!(worker.data_is_empty(), ClockworkError::UnauthorizedWrite);
+
+        Ok(())
+    }
+
+    /// Resolve an instruction to run on behalf of the queue. Buffered instructions are loaded from their instruction
+    /// buffer account, which must be one of the given accounts and belong to the queue's authority.
+    pub fn resolve_instruction(
+        &self,
+        instruction: &QueueInstruction,
+        account_infos: &[AccountInfo],
+    ) -> Result<InstructionData> {
+        let buffer_pubkey = match instruction {
+            QueueInstruction::Buffered { buffer } => buffer,
+            QueueInstruction::Inline(instruction) => return Ok(instruction.clone()),
+        };
+        let account_info = account_infos
+            .iter()
+            .find(|account_info| buffer_pubkey.eq(account_info.key))
+            .ok_or(ClockworkError::InvalidInstructionBuffer)?;
+        require!(
+            account_info.owner.eq(&crate::ID),
+            ClockworkError::InvalidInstructionBuffer
+        );
+        let (buffer, instruction) = InstructionBuffer::try_parse(&account_info.try_borrow_data()?)?;
+        require!(
+            buffer.authority.eq(&self.authority),
+            ClockworkError::InvalidInstructionBuffer
+        );
+        Ok(instruction)
+    }
+
+    /// Count a new thread execution against the execution rate limit. Returns the execution count and start of the
+    /// current rate limit window, or an error if the window's executions have been exhausted.
+    pub fn count_exec_in_window(&self, clock: &Clock) -> Result<(u64, u64)> {
+        let exec_rate_limit = match self.exec_rate_limit {
+            None => return Ok((0, 0)),
+            Some(exec_rate_limit) => exec_rate_limit,
+        };
+
+        // Start a new window if the current one has elapsed.
+        let (now, window_length) = match exec_rate_limit.window {
+            ExecWindow::Seconds { seconds } => (clock.unix_timestamp as u64, seconds),
+            ExecWindow::Slots { slots } => (clock.slot, slots),
+        };
+        let (window_exec_count, window_started_at) = match self.exec_context {
+            Some(exec_context)
+                if now < exec_context.window_started_at.saturating_add(window_length) =>
+            {
+                (
+                    exec_context.window_exec_count,
+                    exec_context.window_started_at,
+                )
+            }
+            _ => (0, now),
+        };
+
+        // Verify the window has executions remaining.
+        require!(
+            window_exec_count < exec_rate_limit.max_executions,
+            ClockworkError::RateLimitExeceeded
+        );
+        Ok((window_exec_count.checked_add(1).unwrap(), window_started_at))
+    }
+
+    /// The context of the event which last triggered the queue. None if the queue has not been triggered since its
+    /// trigger was last set.
+    pub fn trigger_context(&self) -> Option<TriggerContext> {
+        self.exec_context
+            .and_then(|exec_context| exec_context.trigger_context)
+    }
+
+    /// The little-endian bytes to fill a placeholder of the given kind with.
+    fn placeholder_value(&self, value: PlaceholderValue) -> Result<[u8; 8]> {
+        Ok(match value {
+            PlaceholderValue::CronStartedAt => match self.trigger_context() {
+                Some(TriggerContext::Cron { started_at }) => started_at.to_le_bytes(),
+                _ => return Err(ClockworkError::InvalidPlaceholder.into()),
+            },
+            PlaceholderValue::ExecCount => self.exec_count.to_le_bytes(),
+            PlaceholderValue::Slot => Clock::get().unwrap().slot.to_le_bytes(),
+            PlaceholderValue::UnixTimestamp => Clock::get().unwrap().unix_timestamp.to_le_bytes(),
+        })
+    }
+
+    /// Append a record to the execution history, evicting the oldest records once the history is at capacity.
+    pub fn push_exec_record(&mut self, record: ExecRecord) {
+        if self.exec_history_capacity == 0 {
+            return;
+        }
+        self.exec_history.push(record);
+        self.truncate_exec_history();
+    }
+
+    /// Evict the oldest execution records until the history fits within its capacity.
+    pub fn truncate_exec_history(&mut self) {
+        let excess = self
+            .exec_history
+            .len()
+            .saturating_sub(self.exec_history_capacity as usize);
+        self.exec_history.drain(..excess);
+    }
 }
 
 impl TryFrom<Vec<u8>> for Queue {
@@ -72,25 +415,114 @@ impl PartialEq for Queue {
 
 impl Eq for Queue {}
 
+/// The settings to create a queue with.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct QueueCreateSettings {
+    /// Whether or not the queue should be closed, returning its lamports to the authority, once it has expired.
+    pub auto_close: bool,
+    /// The maximum number of execution records to retain.
+    pub exec_history_capacity: u64,
+    /// The unix timestamp after which no new thread executions may be started.
+    pub expires_at: Option<i64>,
+    /// The id of the queue, given by the authority.
+    pub id: String,
+    /// The ordered list of instructions to kick-off the thread.
+    pub kickoff_instructions: Vec<QueueInstruction>,
+    /// The maximum number of thread executions that may be started.
+    pub max_executions: Option<u64>,
+    /// The worker pool allowed to crank the queue. The pool account must be passed to the instruction.
+    pub pool: Option<Pubkey>,
+    /// The triggering event to kickoff a thread.
+    pub trigger: Trigger,
+}
+
+/// The mutable queue settings. Fields which are none are left unchanged.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, Default)]
+pub struct QueueSettings {
+    /// The address lookup tables workers may use to load the accounts of the queue's cranks.
+    pub address_lookup_tables: Option<Vec<Pubkey>>,
+    /// Whether or not the queue should be closed, returning its lamports to the authority, once it has expired.
+    pub auto_close: Option<bool>,
+    /// The number of compute units to request for each crank transaction.
+    pub compute_unit_limit: Option<Option<u32>>,
+    /// The price, in micro-lamports per compute unit, to pay as a priority fee for each crank transaction.
+    pub compute_unit_price: Option<Option<u64>>,
+    /// The placeholders to fill in the data of the queue's instructions at crank time.
+    pub data_placeholders: Option<Vec<DataPlaceholder>>,
+    /// The instruction to run once the queue has exhausted its retries.
+    pub error_instruction: Option<Option<QueueInstruction>>,
+    /// The maximum number of execution records to retain.
+    pub exec_history_capacity: Option<u64>,
+    /// The maximum number of thread executions which may be started within a window of time.
+    pub exec_rate_limit: Option<Option<ExecRateLimit>>,
+    /// The unix timestamp after which no new thread executions may be started.
+    pub expires_at: Option<Option<i64>>,
+    /// The fee the queue bids to pay per crank.
+    pub fee_bid: Option<Option<u64>>,
+    /// The SPL token mint the queue pays crank fees in.
+    pub fee_mint: Option<Option<Pubkey>>,
+    /// The ordered list of instructions to kick-off the thread.
+    pub kickoff_instructions: Option<Vec<QueueInstruction>>,
+    /// The number of spendable lamports, above the rent-exempt minimum, below which the queue is paused.
+    pub low_balance_threshold: Option<Option<u64>>,
+    /// The maximum number of thread executions that may be started.
+    pub max_executions: Option<Option<u64>>,
+    /// The number of times a failing crank may be retried before the queue is paused or its error instruction is run.
+    pub max_retries: Option<u64>,
+    /// The worker pool allowed to crank the queue. The pool account must be passed to the instruction.
+    pub pool: Option<Option<Pubkey>>,
+    /// The maximum number of cranks allowed per slot.
+    pub rate_limit: Option<u64>,
+    /// The triggering event to kickoff a thread. Updating the trigger resets the exec context.
+    pub trigger: Option<Trigger>,
+}
+
 /// Trait for reading and writing to a queue account.
 pub trait QueueAccount {
     /// Get the pubkey of the queue account.
     fn pubkey(&self) -> Pubkey;
 
     /// Initialize the account to hold queue object.
-    fn init(
+    fn init(&mut self, authority: Pubkey, settings: QueueCreateSettings) -> Result<()>;
+
+    /// Crank the queue. Call out to the target programs, parse their responses for the next instructions,
+    /// and apply any queue mutations they request. Returns the address to close the queue to, if one was requested.
+    /// The worker is reimbursed for the lamports it has spent since its balance was recorded, which must be before
+    /// the queue's predicate, if any, was invoked.
+    fn crank(
         &mut self,
-        authority: Pubkey,
-        id: String,
-        kickoff_instruction: InstructionData,
-        trigger: Trigger,
-    ) -> Result<()>;
+        account_infos: &[AccountInfo],
+        bump: u8,
+        worker: &Signer,
+        worker_lamports_pre: u64,
+    ) -> Result<Option<Pubkey>>;
+
+    /// Debit lamports from the queue, drawing down its sponsored lamports if they exceed what remains spendable.
+    /// Fails if the queue would be left below its rent-exempt minimum.
+    fn debit(&mut self, amount: u64) -> Result<()>;
 
-    /// Crank the queue. Call out to the target program and parse the response for a next instruction.
-    fn crank(&mut self, account_infos: &[AccountInfo], bump: u8, worker: &Signer) -> Result<()>;
+    /// Move the lamports held back for the queue's sponsors into its funding account, so they may be reclaimed
+    /// once the queue is closed. The funding account must be provided if the queue has sponsors. The queue may be
+    /// left below its rent-exempt minimum, so this may only be called while closing the queue.
+    fn escrow_sponsored_lamports(&mut self, account_infos: &[AccountInfo]) -> Result<()>;
 
-    /// Reallocate the memory allocation for the account.
+    /// Reallocate the memory allocation for the account, reserving space for the execution history to fill its capacity.
     fn realloc(&mut self) -> Result<()>;
+
+    /// The number of lamports the queue needs to stay rent-exempt without spending the lamports held back for its
+    /// sponsors.
+    fn rent_shortfall(&self) -> Result<u64>;
+
+    /// The number of lamports the queue holds above its rent-exempt minimum.
+    fn spendable_balance(&self) -> Result<u64>;
+
+    /// Verify the queue's trigger has fired. Returns the context of the triggering event, to start a new thread with.
+    fn verify_trigger(
+        &self,
+        account_infos: &[AccountInfo],
+        data_hash: Option<u64>,
+        worker: &Signer,
+    ) -> Result<TriggerContext>;
 }
 
 impl QueueAccount for Account<'_, Queue> {
@@ -98,83 +530,103 @@ impl QueueAccount for Account<'_, Queue> {
         Queue::pubkey(self.authority, self.id.clone())
     }
 
-    fn init(
-        &mut self,
-        authority: Pubkey,
-        id: String,
-        kickoff_instruction: InstructionData,
-        trigger: Trigger,
-    ) -> Result<()> {
+    fn init(&mut self, authority: Pubkey, settings: QueueCreateSettings) -> Result<()> {
+        self.address_lookup_tables = vec![];
         self.authority = authority.key();
+        self.auto_close = settings.auto_close;
+        self.completion_count = 0;
+        self.compute_unit_limit = None;
+        self.compute_unit_price = None;
+        self.consecutive_failures = 0;
         self.created_at = Clock::get().unwrap().into();
+        self.data_placeholders = vec![];
+        self.error_instruction = None;
         self.exec_context = None;
-        self.id = id;
-        self.kickoff_instruction = kickoff_instruction;
-        self.next_instruction = None;
+        self.exec_count = 0;
+        self.exec_history = vec![];
+        self.exec_history_capacity = settings.exec_history_capacity;
+        self.exec_rate_limit = None;
+        self.expires_at = settings.expires_at;
+        self.fee_bid = None;
+        self.fee_mint = None;
+        self.id = settings.id;
+        self.kickoff_instructions = settings.kickoff_instructions;
+        self.last_failure_at = None;
+        self.low_balance_threshold = None;
+        self.max_executions = settings.max_executions;
+        self.max_retries = DEFAULT_MAX_RETRIES;
+        self.next_instructions = vec![];
         self.paused = false;
+        self.pool = settings.pool;
         self.rate_limit = DEFAULT_RATE_LIMIT;
-        self.trigger = trigger;
+        self.sponsored_lamports = 0;
+        self.trigger = settings.trigger;
         Ok(())
     }
 
-    fn crank(&mut self, account_infos: &[AccountInfo], bump: u8, worker: &Signer) -> Result<()> {
-        // Record the worker's lamports before invoking inner ixs
-        let worker_lamports_pre = worker.lamports();
+    fn crank(
+        &mut self,
+        account_infos: &[AccountInfo],
+        bump: u8,
+        worker: &Signer,
+        worker_lamports_pre: u64,
+    ) -> Result<Option<Pubkey>> {
+        // Get the instructions to crank
+        let instructions = if self.next_instructions.is_empty() {
+            self.kickoff_instructions.clone()
+        } else {
+            self.next_instructions.clone()
+        };
 
-        // Get the instruction to crank
-        let kickoff_instruction: &InstructionData = &self.clone().kickoff_instruction;
-        let next_instruction: &Option<InstructionData> = &self.clone().next_instruction;
-        let instruction = next_instruction.as_ref().unwrap_or(kickoff_instruction);
+        // Invoke the instructions in order, merging their crank responses.
+        // Where several responses request the same mutation, the latest one wins.
+        let mut response = CrankResponse::default();
+        for instruction in instructions.iter() {
+            // Invoke the provided instruction, loading it from its buffer if needed
+            let instruction = &self.resolve_instruction(instruction, account_infos)?;
+            self.invoke(instruction, account_infos, bump, worker)?;
 
-        // Inject the worker's pubkey for the Clockwork payer ID
-        let normalized_accounts: &mut Vec<AccountMeta> = &mut vec![];
-        instruction.accounts.iter().for_each(|acc| {
-            let acc_pubkey = if acc.pubkey == crate::utils::PAYER_PUBKEY {
-                worker.key()
-            } else {
-                acc.pubkey
+            // Parse the crank response
+            match get_return_data() {
+                None => {}
+                Some((program_id, return_data)) => {
+                    require!(
+                        program_id.eq(&instruction.program_id),
+                        ClockworkError::InvalidCrankResponse
+                    );
+                    let crank_response = CrankResponse::try_from_return_data(&return_data)?;
+                    response.close_to = crank_response.close_to.or(response.close_to);
+                    response.kickoff_instructions = crank_response
+                        .kickoff_instructions
+                        .or(response.kickoff_instructions);
+                    response
+                        .next_instructions
+                        .extend(crank_response.next_instructions);
+                    response.pause = response.pause || crank_response.pause;
+                    response.trigger = crank_response.trigger.or(response.trigger);
+                }
             };
-            normalized_accounts.push(AccountMeta {
-                pubkey: acc_pubkey,
-                is_signer: acc.is_signer,
-                is_writable: acc.is_writable,
-            });
-        });
+        }
+        self.next_instructions = response.next_instructions;
 
-        // Invoke the provided instruction
-        invoke_signed(
-            &Instruction {
-                program_id: instruction.program_id,
-                data: instruction.data.clone(),
-                accounts: normalized_accounts.to_vec(),
-            },
-            account_infos,
-            &[&[
-                SEED_QUEUE,
-                self.authority.as_ref(),
-                self.id.as_bytes(),
-                &[bump],
-            ]],
-        )?;
+        // If requested, replace the kickoff instructions
+        if let Some(kickoff_instructions) = response.kickoff_instructions {
+            self.kickoff_instructions = kickoff_instructions;
+        }
 
-        // Verify that the inner ix did not write data to the worker address
-        require!(worker.data_is_empty(), ClockworkError::UnauthorizedWrite);
+        // If requested, pause the queue
+        if response.pause {
+            self.paused = true;
+        }
 
-        // Parse the crank response
-        match get_return_data() {
-            None => {
-                self.next_instruction = None;
-            }
-            Some((program_id, return_data)) => {
-                require!(
-                    program_id.eq(&instruction.program_id),
-                    ClockworkError::InvalidCrankResponse
-                );
-                let crank_response = CrankResponse::try_from_slice(return_data.as_slice())
-                    .map_err(|_err| ClockworkError::InvalidCrankResponse)?;
-                self.next_instruction = crank_response.next_instruction;
-            }
-        };
+        // If the thread has completed, increment the completion count
+        if self.next_instructions.is_empty() {
+            self.completion_count = self.completion_count.checked_add(1).unwrap();
+        }
+
+        // Reset the failure count
+        self.consecutive_failures = 0;
+        self.last_failure_at = None;
 
         // Increment the crank count
         let current_slot = Clock::get().unwrap().slot;
@@ -183,66 +635,527 @@ impl QueueAccount for Account<'_, Queue> {
             Some(exec_context) => {
                 // Update the exec context
                 self.exec_context = Some(ExecContext {
-                    cranks_since_reimbursement: exec_context
-                        .cranks_since_reimbursement
-                        .checked_add(1)
-                        .unwrap(),
                     cranks_since_slot: if current_slot == exec_context.last_crank_at {
                         exec_context.cranks_since_slot.checked_add(1).unwrap()
                     } else {
                         1
                     },
                     last_crank_at: current_slot,
-                    trigger_context: exec_context.trigger_context,
+                    ..exec_context
                 });
             }
         }
 
+        // If requested, swap the trigger. This ends the current thread and clears the trigger context, while the rest
+        // of the exec context, and with it the rate limit window, is kept. An immediate trigger would fire again as
+        // soon as the trigger context is cleared, so it may not be swapped in.
+        if let Some(trigger) = response.trigger {
+            require!(
+                !matches!(trigger, Trigger::Immediate),
+                ClockworkError::InvalidCrankResponse
+            );
+            trigger.validate()?;
+            self.exec_context = self.exec_context.map(|exec_context| ExecContext {
+                trigger_context: None,
+                ..exec_context
+            });
+            self.next_instructions = vec![];
+            self.trigger = trigger;
+        }
+
         // Realloc the queue account
         self.realloc()?;
 
         // Reimbursement worker for lamports paid during inner ix
-        let worker_lamports_post = worker.lamports();
-        let worker_reimbursement = worker_lamports_pre
-            .checked_sub(worker_lamports_post)
-            .unwrap();
+        reimburse_worker(self, worker, worker_lamports_pre)?;
+
+        Ok(response.close_to)
+    }
+
+    fn debit(&mut self, amount: u64) -> Result<()> {
+        require!(
+            amount.le(&self.spendable_balance()?),
+            ClockworkError::InsufficientFunds
+        );
         **self.to_account_info().try_borrow_mut_lamports()? = self
             .to_account_info()
             .lamports()
-            .checked_sub(worker_reimbursement)
-            .unwrap();
-        **worker.to_account_info().try_borrow_mut_lamports()? = worker
-            .to_account_info()
-            .lamports()
-            .checked_add(worker_reimbursement)
+            .checked_sub(amount)
             .unwrap();
 
+        // Draw down the sponsored lamports so the sponsors are never owed more than the queue can spend.
+        self.sponsored_lamports = self.sponsored_lamports.min(self.spendable_balance()?);
+        Ok(())
+    }
+
+    fn escrow_sponsored_lamports(&mut self, account_infos: &[AccountInfo]) -> Result<()> {
+        // Exit early if the queue has no sponsors
+        if self.sponsored_lamports == 0 {
+            return Ok(());
+        }
+
+        // Get the queue's funding account
+        let funding_pubkey = Funding::pubkey(self.key(), self.created_at.slot);
+        let funding_account_info = account_infos
+            .iter()
+            .find(|account_info| funding_pubkey.eq(account_info.key))
+            .ok_or(ClockworkError::InvalidFundingAccount)?;
+        let mut funding = Account::<Funding>::try_from(funding_account_info)?;
+
+        // Move the sponsors' unspent lamports to the funding account, whatever the queue's spendable balance
+        let queue_account_info = self.to_account_info();
+        let amount = self.sponsored_lamports.min(queue_account_info.lamports());
+        **queue_account_info.try_borrow_mut_lamports()? =
+            queue_account_info.lamports().checked_sub(amount).unwrap();
+        **funding_account_info.try_borrow_mut_lamports()? =
+            funding_account_info.lamports().checked_add(amount).unwrap();
+        funding.is_closed = true;
+        funding.reclaimable = funding.reclaimable.checked_add(amount).unwrap();
+        funding.exit(&crate::ID)?;
+        self.sponsored_lamports = 0;
+
         Ok(())
     }
 
     fn realloc(&mut self) -> Result<()> {
         // Realloc memory for the queue account
-        let data_len = 8 + self.try_to_vec()?.len();
+        let data_len = 8 + self.try_to_vec()?.len() + self.exec_history_reserved_len();
         self.to_account_info().realloc(data_len, false)?;
         Ok(())
     }
+
+    fn rent_shortfall(&self) -> Result<u64> {
+        let account_info = self.to_account_info();
+        let minimum_balance = Rent::get()?.minimum_balance(account_info.data_len());
+        Ok(minimum_balance
+            .checked_add(self.sponsored_lamports)
+            .unwrap()
+            .saturating_sub(account_info.lamports()))
+    }
+
+    fn spendable_balance(&self) -> Result<u64> {
+        let account_info = self.to_account_info();
+        let minimum_balance = Rent::get()?.minimum_balance(account_info.data_len());
+        Ok(account_info.lamports().saturating_sub(minimum_balance))
+    }
+
+    fn verify_trigger(
+        &self,
+        account_infos: &[AccountInfo],
+        data_hash: Option<u64>,
+        worker: &Signer,
+    ) -> Result<TriggerContext> {
+        let current_slot = Clock::get().unwrap().slot;
+        match self.trigger.clone() {
+            Trigger::Account {
+                pubkey,
+                offset,
+                size,
+            } => {
+                // Require the provided data hash is non-null.
+                let data_hash = match data_hash {
+                    None => return Err(ClockworkError::InvalidQueueState.into()),
+                    Some(data_hash) => data_hash,
+                };
+
+                // Verify the first remaining account is the account this queue is listening for.
+                let account_info = account_infos
+                    .first()
+                    .ok_or(ClockworkError::InvalidTrigger)?;
+                require!(pubkey.eq(account_info.key), ClockworkError::InvalidTrigger);
+
+                // Verify the data hash provided by the worker is equal to the hash of the watched bytes.
+                let data = &account_info.try_borrow_data().unwrap();
+                require!(
+                    data_hash.eq(&account_data_hash(data, offset, size)),
+                    ClockworkError::InvalidTrigger
+                );
+
+                // Verify the watched bytes have changed since the queue was last triggered.
+                match self.trigger_context() {
+                    None => {
+                        // This queue has not begun executing yet.
+                        // There is no prior data hash to compare against.
+                    }
+                    Some(TriggerContext::Account {
+                        data_hash: prior_data_hash,
+                    }) => {
+                        require!(
+                            data_hash.ne(&prior_data_hash),
+                            ClockworkError::InvalidTrigger
+                        );
+                    }
+                    Some(_) => return Err(ClockworkError::InvalidQueueState.into()),
+                }
+
+                // Return the trigger context with the new data hash.
+                Ok(TriggerContext::Account { data_hash })
+            }
+            Trigger::Accounts { accounts, mode } => {
+                // Verify the first remaining accounts are the accounts this queue is listening for, in order,
+                // and hash the watched bytes of each.
+                let mut data_hashes = [0; MAX_TRIGGER_ACCOUNTS];
+                for (i, watched_account) in accounts.iter().enumerate() {
+                    let account_info =
+                        account_infos.get(i).ok_or(ClockworkError::InvalidTrigger)?;
+                    require!(
+                        watched_account.pubkey.eq(account_info.key),
+                        ClockworkError::InvalidTrigger
+                    );
+                    data_hashes[i] = account_data_hash(
+                        &account_info.try_borrow_data().unwrap(),
+                        watched_account.offset,
+                        watched_account.size,
+                    );
+                }
+
+                // Verify the watched bytes of any, or all, of the accounts have changed since the queue was last triggered.
+                match self.trigger_context() {
+                    None => {
+                        // This queue has not begun executing yet.
+                        // There are no prior data hashes to compare against.
+                    }
+                    Some(TriggerContext::Accounts {
+                        data_hashes: prior_data_hashes,
+                    }) => {
+                        let mut changes = data_hashes
+                            .iter()
+                            .zip(prior_data_hashes.iter())
+                            .take(accounts.len())
+                            .map(|(data_hash, prior_data_hash)| data_hash.ne(prior_data_hash));
+                        let is_triggered = match mode {
+                            AccountsTriggerMode::All => changes.all(|is_changed| is_changed),
+                            AccountsTriggerMode::Any => changes.any(|is_changed| is_changed),
+                        };
+                        require!(is_triggered, ClockworkError::InvalidTrigger);
+                    }
+                    Some(_) => return Err(ClockworkError::InvalidQueueState.into()),
+                }
+
+                // Return the trigger context with the new data hashes.
+                Ok(TriggerContext::Accounts { data_hashes })
+            }
+            Trigger::Condition { predicate } => {
+                // Invoke the predicate and verify it returned true. The predicate is invoked without the queue's
+                // signature, so it cannot act on the queue's behalf.
+                let predicate = self.resolve_instruction(&predicate, account_infos)?;
+                self.invoke_unsigned(&predicate, account_infos, worker)?;
+                let is_satisfied = match get_return_data() {
+                    None => false,
+                    Some((program_id, return_data)) => {
+                        program_id.eq(&predicate.program_id)
+                            && bool::try_from_slice(&return_data).unwrap_or(false)
+                    }
+                };
+                require!(is_satisfied, ClockworkError::InvalidTrigger);
+
+                // Return the trigger context.
+                Ok(TriggerContext::Condition {
+                    started_at: current_slot,
+                })
+            }
+            Trigger::Cron {
+                schedule,
+                skippable,
+                timezone,
+            } => {
+                // Get the reference timestamp for calculating the queue's scheduled target timestamp.
+                let reference_timestamp = match self.trigger_context() {
+                    None => self.created_at.unix_timestamp,
+                    Some(TriggerContext::Cron { started_at }) => started_at,
+                    Some(_) => return Err(ClockworkError::InvalidQueueState.into()),
+                };
+
+                // Verify the current timestamp is greater than or equal to the threshold timestamp.
+                let current_timestamp = Clock::get().unwrap().unix_timestamp;
+                let threshold_timestamp =
+                    next_cron_timestamp(reference_timestamp, &schedule, timezone.as_deref())
+                        .ok_or(ClockworkError::InvalidTrigger)?;
+                require!(
+                    current_timestamp >= threshold_timestamp,
+                    ClockworkError::InvalidTrigger
+                );
+
+                // If the schedule is marked as skippable, set the started_at of the exec context
+                // to be the threshold moment just before the current timestamp.
+                let started_at = if skippable && current_timestamp > threshold_timestamp {
+                    prev_cron_timestamp(current_timestamp, &schedule, timezone.as_deref())
+                        .ok_or(ClockworkError::InvalidTrigger)?
+                } else {
+                    threshold_timestamp
+                };
+
+                // Return the trigger context.
+                Ok(TriggerContext::Cron { started_at })
+            }
+            Trigger::Epoch { epoch } => {
+                // Verify the queue has not already been triggered and the cluster has reached the target epoch.
+                require!(
+                    self.trigger_context().is_none(),
+                    ClockworkError::InvalidQueueState
+                );
+                require!(
+                    Clock::get().unwrap().epoch >= epoch,
+                    ClockworkError::InvalidTrigger
+                );
+
+                // Return the trigger context.
+                Ok(TriggerContext::Epoch { started_at: epoch })
+            }
+            Trigger::Immediate => {
+                // Verify the queue has not already been triggered.
+                require!(
+                    self.trigger_context().is_none(),
+                    ClockworkError::InvalidQueueState
+                );
+                Ok(TriggerContext::Immediate)
+            }
+            Trigger::Queue {
+                queue: followed_queue_pubkey,
+            } => {
+                // Verify the first remaining account is the queue this queue is following.
+                let account_info = account_infos
+                    .first()
+                    .ok_or(ClockworkError::InvalidTrigger)?;
+                require!(
+                    followed_queue_pubkey.eq(account_info.key),
+                    ClockworkError::InvalidTrigger
+                );
+                let followed_queue = Account::<Queue>::try_from(account_info)?;
+
+                // Verify the followed queue has completed a thread execution since this queue was last triggered.
+                let prior_completion_count = match self.trigger_context() {
+                    None => 0,
+                    Some(TriggerContext::Queue { completion_count }) => completion_count,
+                    Some(_) => return Err(ClockworkError::InvalidQueueState.into()),
+                };
+                require!(
+                    followed_queue.completion_count > prior_completion_count,
+                    ClockworkError::InvalidTrigger
+                );
+
+                // Return the trigger context.
+                Ok(TriggerContext::Queue {
+                    completion_count: followed_queue.completion_count,
+                })
+            }
+            Trigger::Slot { slot } => {
+                // Verify the queue has not already been triggered and the cluster has reached the target slot.
+                require!(
+                    self.trigger_context().is_none(),
+                    ClockworkError::InvalidQueueState
+                );
+                require!(current_slot >= slot, ClockworkError::InvalidTrigger);
+
+                // Return the trigger context.
+                Ok(TriggerContext::Slot { started_at: slot })
+            }
+        }
+    }
+}
+
+/// Verify a worker pool named by a queue is a pool account, which must be one of the given accounts.
+pub fn verify_pool(account_infos: &[AccountInfo], pool: Pubkey) -> Result<()> {
+    let account_info = account_infos
+        .iter()
+        .find(|account_info| pool.eq(account_info.key))
+        .ok_or(ClockworkError::InvalidPool)?;
+    Account::<Pool>::try_from(account_info).map_err(|_err| ClockworkError::InvalidPool)?;
+    Ok(())
+}
+
+/// Close a queue account, returning its lamports to the close_to account. The sponsors' unspent lamports are held back
+/// in the queue's funding account for them to reclaim. If the queue pays crank fees in tokens and its token account,
+/// its authority's token account, and the token program are among the given accounts, the remaining tokens are swept
+/// to the authority and the queue's token account is closed too. Otherwise the tokens are left in place, and may be
+/// withdrawn if a queue is re-created at the same address.
+pub fn close_queue<'info>(
+    account_infos: &[AccountInfo<'info>],
+    bump: u8,
+    close_to: AccountInfo<'info>,
+    queue: &mut Account<'info, Queue>,
+) -> Result<()> {
+    // Sweep the queue's tokens to its authority and close its token account
+    if let Some(mint) = queue.fee_mint {
+        let find_account_info = |pubkey: Pubkey| {
+            account_infos
+                .iter()
+                .find(|account_info| pubkey.eq(account_info.key))
+                .cloned()
+        };
+        if let (Some(queue_tokens), Some(authority_tokens), Some(token_program)) = (
+            find_account_info(get_associated_token_address(&queue.key(), &mint)),
+            find_account_info(get_associated_token_address(&queue.authority, &mint)),
+            find_account_info(token::ID),
+        ) {
+            // Skip the sweep if either token account has not been created.
+            if queue_tokens.owner.eq(&token::ID) && authority_tokens.owner.eq(&token::ID) {
+                let amount = Account::<TokenAccount>::try_from(&queue_tokens)?.amount;
+                let seeds: &[&[u8]] = &[
+                    SEED_QUEUE,
+                    queue.authority.as_ref(),
+                    queue.id.as_bytes(),
+                    &[bump],
+                ];
+                if amount > 0 {
+                    transfer(
+                        CpiContext::new_with_signer(
+                            token_program.clone(),
+                            Transfer {
+                                from: queue_tokens.clone(),
+                                to: authority_tokens,
+                                authority: queue.to_account_info(),
+                            },
+                            &[seeds],
+                        ),
+                        amount,
+                    )?;
+                }
+                close_account(CpiContext::new_with_signer(
+                    token_program,
+                    CloseAccount {
+                        account: queue_tokens,
+                        destination: close_to.clone(),
+                        authority: queue.to_account_info(),
+                    },
+                    &[seeds],
+                ))?;
+            }
+        }
+    }
+
+    // Hold back the sponsors' unspent lamports for them to reclaim. This is done last, as it may leave the queue
+    // below its rent-exempt minimum until it is closed.
+    queue.escrow_sponsored_lamports(account_infos)?;
+
+    // Close the queue account
+    queue.close(close_to)
+}
+
+/// Reimburse a worker from the queue's balance for the lamports they spent since their balance was recorded.
+fn reimburse_worker(
+    queue: &mut Account<Queue>,
+    worker: &AccountInfo,
+    worker_lamports_pre: u64,
+) -> Result<()> {
+    let worker_lamports_post = worker.lamports();
+    let worker_reimbursement = worker_lamports_pre.saturating_sub(worker_lamports_post);
+    queue.debit(worker_reimbursement)?;
+    **worker.try_borrow_mut_lamports()? =
+        worker.lamports().checked_add(worker_reimbursement).unwrap();
+    Ok(())
 }
 
-/// A response value target programs can return to update the queue.
-#[derive(AnchorDeserialize, AnchorSerialize, Clone, Debug)]
+/// The version byte which prefixes a serialized crank response. It is distinct from the first byte of the legacy
+/// `Option<InstructionData>` format, which is always 0 or 1.
+pub const CRANK_RESPONSE_VERSION: u8 = 2;
+
+/// A response value target programs can return to update the queue. It is serialized with a leading version byte.
+#[derive(Clone, Debug)]
 pub struct CrankResponse {
-    /// The next instruction to set on the queue.
-    pub next_instruction: Option<InstructionData>,
+    /// The address to close the queue to once the crank has completed. The account must be
+    /// one of the accounts passed to the crank, such as one of the returning instruction's accounts.
+    pub close_to: Option<Pubkey>,
+    /// The instructions to replace the queue's kickoff instructions with.
+    pub kickoff_instructions: Option<Vec<QueueInstruction>>,
+    /// The next instructions to set on the queue. These are appended to the
+    /// instructions returned by any earlier instruction in the same crank.
+    pub next_instructions: Vec<QueueInstruction>,
+    /// Whether or not to pause the queue.
+    pub pause: bool,
+    /// The trigger to replace the queue's trigger with. This ends the current thread. May not be an immediate trigger.
+    pub trigger: Option<Trigger>,
+}
+
+impl CrankResponse {
+    /// Parse a crank response from a target program's return data. Responses in the legacy
+    /// `Option<InstructionData>` format are also accepted, and are told apart by their first byte.
+    pub fn try_from_return_data(data: &[u8]) -> Result<Self> {
+        match data.first() {
+            Some(&CRANK_RESPONSE_VERSION) => CrankResponse::try_from_slice(data),
+            Some(0) | Some(1) => {
+                Option::<InstructionData>::try_from_slice(data).map(|next_instruction| {
+                    CrankResponse {
+                        next_instructions: next_instruction
+                            .into_iter()
+                            .map(QueueInstruction::Inline)
+                            .collect(),
+                        ..CrankResponse::default()
+                    }
+                })
+            }
+            _ => return Err(ClockworkError::InvalidCrankResponse.into()),
+        }
+        .map_err(|_err| ClockworkError::InvalidCrankResponse.into())
+    }
+}
+
+impl AnchorSerialize for CrankResponse {
+    fn serialize<W: Write>(&self, writer: &mut W) -> std::io::Result<()> {
+        CRANK_RESPONSE_VERSION.serialize(writer)?;
+        self.close_to.serialize(writer)?;
+        self.kickoff_instructions.serialize(writer)?;
+        self.next_instructions.serialize(writer)?;
+        self.pause.serialize(writer)?;
+        self.trigger.serialize(writer)
+    }
+}
+
+impl AnchorDeserialize for CrankResponse {
+    fn deserialize(buf: &mut &[u8]) -> std::io::Result<Self> {
+        let version = u8::deserialize(buf)?;
+        if version != CRANK_RESPONSE_VERSION {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Unsupported crank response version",
+            ));
+        }
+        Ok(Self {
+            close_to: AnchorDeserialize::deserialize(buf)?,
+            kickoff_instructions: AnchorDeserialize::deserialize(buf)?,
+            next_instructions: AnchorDeserialize::deserialize(buf)?,
+            pause: AnchorDeserialize::deserialize(buf)?,
+            trigger: AnchorDeserialize::deserialize(buf)?,
+        })
+    }
 }
 
 impl Default for CrankResponse {
     fn default() -> Self {
         return Self {
-            next_instruction: None,
+            close_to: None,
+            kickoff_instructions: None,
+            next_instructions: vec![],
+            pause: false,
+            trigger: None,
         };
     }
 }
 
+/// A placeholder in the data of a queue's instructions, filled at crank time.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct DataPlaceholder {
+    /// The byte offset in the instruction data to write the value at.
+    pub offset: u64,
+    /// The program whose instructions the placeholder applies to.
+    pub program_id: Pubkey,
+    /// The value to write. Values are written as 8 little-endian bytes.
+    pub value: PlaceholderValue,
+}
+
+/// The values which can fill an instruction data placeholder.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PlaceholderValue {
+    /// The scheduled moment (`i64` unix timestamp) of the current cron thread execution.
+    CronStartedAt,
+    /// The number of thread executions that have been started (`u64`).
+    ExecCount,
+    /// The current slot (`u64`).
+    Slot,
+    /// The current unix timestamp (`i64`).
+    UnixTimestamp,
+}
+
 /// The triggering conditions of a queue.
 #[derive(AnchorDeserialize, AnchorSerialize, Debug, Clone)]
 pub enum Trigger {
@@ -250,6 +1163,12 @@ pub enum Trigger {
     Account {
         /// The address of the account to subscribe to.
         pubkey: Pubkey,
+
+        /// The byte offset of the account data to watch. If none, the data is watched from the first byte.
+        offset: Option<u64>,
+
+        /// The number of bytes to watch, starting at the offset. If none, the data is watched through the last byte.
+        size: Option<u64>,
     },
 
     /// Allows a queue to be cranked according to a one-time or recurring schedule.
@@ -260,34 +1179,175 @@ pub enum Trigger {
         /// Boolean value indicating whether triggering moments may be skipped if they are missed (e.g. due to network downtime).
         /// If false, any "missed" triggering moments will simply be cranked as soon as the network comes back online.
         skippable: bool,
+
+        /// The timezone to evaluate the schedule in, as an IANA name (e.g. "America/New_York") or a fixed UTC offset
+        /// (e.g. "+05:30"). If none, the schedule is evaluated in UTC. Only the IANA names listed in the workspace's
+        /// `.cargo/config.toml` are supported. See `utils::next_cron_timestamp` for how daylight saving time
+        /// transitions are handled.
+        timezone: Option<String>,
     },
 
     /// Allows a queue to be cranked as soon as it's created.
     Immediate,
+
+    /// Allows a queue to be cranked once the cluster has reached a particular slot.
+    Slot {
+        /// The slot to wait for.
+        slot: u64,
+    },
+
+    /// Allows a queue to be cranked once the cluster has reached a particular epoch.
+    Epoch {
+        /// The epoch to wait for.
+        epoch: u64,
+    },
+
+    /// Allows a queue to be cranked whenever a predicate instruction returns true.
+    Condition {
+        /// The instruction to invoke. Its return data must decode to a `bool`.
+        predicate: QueueInstruction,
+    },
+
+    /// Allows a queue to be cranked whenever another queue completes a thread execution.
+    /// A queue which has not yet executed may be cranked once the other queue has completed at least one execution.
+    Queue {
+        /// The queue to follow.
+        queue: Pubkey,
+    },
+
+    /// Allows a queue to subscribe to several accounts and be cranked whenever the data of any of them, or all of
+    /// them, has changed since the queue was last triggered.
+    Accounts {
+        /// The accounts to subscribe to, in the order they must be passed to the crank.
+        accounts: Vec<WatchedAccount>,
+
+        /// Whether a change to any one of the accounts triggers the queue, or only a change to all of them.
+        mode: AccountsTriggerMode,
+    },
+}
+
+impl Trigger {
+    /// Verify the trigger can be evaluated. Cron triggers must have a parsable timezone, if one is provided,
+    /// and account triggers must watch at least one and at most `MAX_TRIGGER_ACCOUNTS` accounts.
+    pub fn validate(&self) -> Result<()> {
+        match self {
+            Trigger::Accounts { accounts, .. } => {
+                require!(
+                    !accounts.is_empty() && accounts.len().le(&MAX_TRIGGER_ACCOUNTS),
+                    ClockworkError::InvalidTriggerAccounts
+                );
+            }
+            Trigger::Cron {
+                timezone: Some(timezone),
+                ..
+            } => {
+                require!(
+                    CronTimezone::from_str(timezone).is_ok(),
+                    ClockworkError::InvalidTimezone
+                );
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+}
+
+/// An account watched by an accounts trigger.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub struct WatchedAccount {
+    /// The byte offset of the account data to watch. If none, the data is watched from the first byte.
+    pub offset: Option<u64>,
+    /// The address of the account.
+    pub pubkey: Pubkey,
+    /// The number of bytes to watch, starting at the offset. If none, the data is watched through the last byte.
+    pub size: Option<u64>,
+}
+
+/// How the changes to the accounts watched by an accounts trigger combine to trigger a queue.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AccountsTriggerMode {
+    /// The queue is triggered once every watched account has changed.
+    All,
+    /// The queue is triggered once any watched account has changed.
+    Any,
 }
 
 /// The execution context of a particular transaction thread.
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub struct ExecContext {
-    /// Number of cranks since the last tx reimbursement.
-    pub cranks_since_reimbursement: u64,
-
     /// Number of cranks in this slot.
     pub cranks_since_slot: u64,
 
     /// Slot of the last crank
     pub last_crank_at: u64,
 
+    /// Context for the triggering condition. None if the queue has not been triggered since its trigger was last set.
+    pub trigger_context: Option<TriggerContext>,
+
+    /// Number of thread executions started in the current rate limit window.
+    pub window_exec_count: u64,
+
+    /// Slot or unix timestamp, per the rate limit's window, at which the current rate limit window started.
+    pub window_started_at: u64,
+}
+
+/// A limit on the number of thread executions a queue may start within a window of time.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct ExecRateLimit {
+    /// The maximum number of thread executions which may be started within a window.
+    pub max_executions: u64,
+    /// The length of a window.
+    pub window: ExecWindow,
+}
+
+impl ExecRateLimit {
+    /// Whether the rate limit allows at least one execution within a non-empty window.
+    pub fn is_valid(&self) -> bool {
+        self.max_executions.gt(&0)
+            && match self.window {
+                ExecWindow::Seconds { seconds } => seconds.gt(&0),
+                ExecWindow::Slots { slots } => slots.gt(&0),
+            }
+    }
+}
+
+/// The length of an execution rate limit window.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub enum ExecWindow {
+    /// A window measured in seconds of unix time.
+    Seconds { seconds: u64 },
+    /// A window measured in slots.
+    Slots { slots: u64 },
+}
+
+/// A record of a transaction thread execution.
+#[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
+pub struct ExecRecord {
+    /// Number of cranks submitted during the execution.
+    pub crank_count: u64,
+
+    /// Slot of the latest crank of the execution.
+    pub ended_at: u64,
+
+    /// Number of lamports charged to the queue during the execution.
+    pub fees_charged: u64,
+
+    /// Slot of the first crank of the execution.
+    pub started_at: u64,
+
     /// Context for the triggering condition
     pub trigger_context: TriggerContext,
+
+    /// The worker who submitted the latest crank of the execution.
+    pub worker: Pubkey,
 }
 
 /// The event which allowed a particular transaction thread to be triggered.
 #[derive(AnchorDeserialize, AnchorSerialize, Clone, Copy, Debug, Hash, PartialEq, Eq)]
 pub enum TriggerContext {
-    /// A running hash of the observed account data.
+    /// A hash of the observed account data.
     Account {
-        /// The account's data hash.
+        /// The hash of the watched bytes at the moment the queue was triggered, as computed by `utils::account_data_hash`.
         data_hash: u64,
     },
 
@@ -299,4 +1359,569 @@ pub enum TriggerContext {
 
     /// The immediate trigger context.
     Immediate,
+
+    /// A slot execution context.
+    Slot {
+        /// The slot the trigger was waiting for.
+        started_at: u64,
+    },
+
+    /// An epoch execution context.
+    Epoch {
+        /// The epoch the trigger was waiting for.
+        started_at: u64,
+    },
+
+    /// A condition execution context.
+    Condition {
+        /// The slot the predicate was observed to return true.
+        started_at: u64,
+    },
+
+    /// A queue execution context.
+    Queue {
+        /// The completion count of the followed queue at the moment of triggering.
+        completion_count: u64,
+    },
+
+    /// The hashes of the data of several observed accounts.
+    Accounts {
+        /// The hashes of the watched bytes of each account at the moment the queue was triggered, in the order of the
+        /// trigger's accounts. Slots past the number of watched accounts are zero.
+        data_hashes: [u64; MAX_TRIGGER_ACCOUNTS],
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use {
+        super::*,
+        crate::objects::{AccountMetaData, Sponsor},
+        anchor_lang::solana_program::{
+            entrypoint::{ProgramResult, SUCCESS},
+            program_stubs, system_instruction, system_program,
+        },
+    };
+
+    struct TestSyscallStubs;
+
+    impl program_stubs::SyscallStubs for TestSyscallStubs {
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Clock) = Clock::default() };
+            SUCCESS
+        }
+
+        fn sol_get_rent_sysvar(&self, var_addr: *mut u8) -> u64 {
+            unsafe { *(var_addr as *mut Rent) = Rent::default() };
+            SUCCESS
+        }
+
+        // Like the runtime, require each signer of an invoked instruction to have signed the transaction or to be
+        // derived from the invoking program's signer seeds. The instruction itself is not executed.
+        fn sol_invoke_signed(
+            &self,
+            instruction: &Instruction,
+            account_infos: &[AccountInfo],
+            signers_seeds: &[&[&[u8]]],
+        ) -> ProgramResult {
+            let signers = signers_seeds
+                .iter()
+                .map(|seeds| Pubkey::create_program_address(seeds, &crate::ID).unwrap())
+                .collect::<Vec<Pubkey>>();
+            for account_meta in instruction.accounts.iter().filter(|meta| meta.is_signer) {
+                let is_signed = signers.contains(&account_meta.pubkey)
+                    || account_infos.iter().any(|account_info| {
+                        account_info.is_signer && account_meta.pubkey.eq(account_info.key)
+                    });
+                if !is_signed {
+                    return Err(ProgramError::MissingRequiredSignature);
+                }
+            }
+            Ok(())
+        }
+    }
+
+    fn account_data<T: AccountSerialize>(account: &T) -> Vec<u8> {
+        let mut data = vec![];
+        account.try_serialize(&mut data).unwrap();
+        data
+    }
+
+    /// A sponsored queue grown by an update, as queue_update's realloc would leave it, with its funding account.
+    fn grown_sponsored_queue(sponsored_lamports: u64) -> (Pubkey, Vec<u8>, u64, Pubkey, Vec<u8>) {
+        program_stubs::set_syscall_stubs(Box::new(TestSyscallStubs));
+        let rent = Rent::default();
+
+        // Fund a queue with the sponsors' lamports on top of its rent-exempt minimum.
+        let mut queue_data = queue(None, None);
+        queue_data.sponsored_lamports = sponsored_lamports;
+        let lamports = rent
+            .minimum_balance(account_data(&queue_data).len())
+            .checked_add(sponsored_lamports)
+            .unwrap();
+
+        // Grow the queue without adding any lamports.
+        queue_data.kickoff_instructions =
+            vec![QueueInstruction::Inline(instruction(vec![0; 1_000]))];
+        let queue_pubkey = Pubkey::new_unique();
+        let funding = Funding {
+            is_closed: false,
+            queue: queue_pubkey,
+            queue_created_at: queue_data.created_at.slot,
+            reclaimable: 0,
+            sponsors: vec![Sponsor {
+                amount: sponsored_lamports,
+                sponsor: Pubkey::new_unique(),
+            }],
+            total_contributed: sponsored_lamports,
+        };
+        (
+            queue_pubkey,
+            account_data(&queue_data),
+            lamports,
+            Funding::pubkey(queue_pubkey, funding.queue_created_at),
+            account_data(&funding),
+        )
+    }
+
+    fn clock(slot: u64, unix_timestamp: i64) -> Clock {
+        Clock {
+            slot,
+            unix_timestamp,
+            ..Clock::default()
+        }
+    }
+
+    fn exec_context(window_exec_count: u64, window_started_at: u64) -> ExecContext {
+        ExecContext {
+            cranks_since_slot: 0,
+            last_crank_at: 0,
+            trigger_context: Some(TriggerContext::Immediate),
+            window_exec_count,
+            window_started_at,
+        }
+    }
+
+    fn instruction(data: Vec<u8>) -> InstructionData {
+        InstructionData {
+            program_id: Pubkey::new_unique(),
+            accounts: vec![AccountMetaData {
+                pubkey: Pubkey::new_unique(),
+                is_signer: false,
+                is_writable: true,
+            }],
+            data,
+        }
+    }
+
+    fn queue(exec_context: Option<ExecContext>, exec_rate_limit: Option<ExecRateLimit>) -> Queue {
+        Queue {
+            address_lookup_tables: vec![],
+            authority: Pubkey::new_unique(),
+            auto_close: false,
+            completion_count: 0,
+            compute_unit_limit: None,
+            compute_unit_price: None,
+            consecutive_failures: 0,
+            created_at: ClockData::from(Clock::default()),
+            data_placeholders: vec![],
+            error_instruction: None,
+            exec_context,
+            exec_count: 0,
+            exec_history: vec![],
+            exec_history_capacity: MAX_EXEC_HISTORY_CAPACITY,
+            exec_rate_limit,
+            expires_at: None,
+            fee_bid: None,
+            fee_mint: None,
+            id: "test".into(),
+            kickoff_instructions: vec![],
+            last_failure_at: None,
+            low_balance_threshold: None,
+            max_executions: None,
+            max_retries: DEFAULT_MAX_RETRIES,
+            next_instructions: vec![],
+            paused: false,
+            pool: None,
+            rate_limit: DEFAULT_RATE_LIMIT,
+            sponsored_lamports: 0,
+            trigger: Trigger::Immediate,
+        }
+    }
+
+    #[test]
+    fn test_crank_response_round_trip() {
+        let close_to = Pubkey::new_unique();
+        let kickoff_instruction = QueueInstruction::Inline(instruction(vec![1, 2, 3]));
+        let next_instructions = vec![
+            QueueInstruction::Inline(instruction(vec![4, 5])),
+            QueueInstruction::Buffered {
+                buffer: Pubkey::new_unique(),
+            },
+        ];
+        let data = CrankResponse {
+            close_to: Some(close_to),
+            kickoff_instructions: Some(vec![kickoff_instruction.clone()]),
+            next_instructions: next_instructions.clone(),
+            pause: true,
+            trigger: Some(Trigger::Slot { slot: 42 }),
+        }
+        .try_to_vec()
+        .unwrap();
+        assert_eq!(data[0], CRANK_RESPONSE_VERSION);
+
+        let response = CrankResponse::try_from_return_data(&data).unwrap();
+        assert_eq!(response.close_to, Some(close_to));
+        assert_eq!(
+            response.kickoff_instructions,
+            Some(vec![kickoff_instruction])
+        );
+        assert_eq!(response.next_instructions, next_instructions);
+        assert!(response.pause);
+        match response.trigger {
+            Some(Trigger::Slot { slot }) => assert_eq!(slot, 42),
+            trigger => panic!("Unexpected trigger {:?}", trigger),
+        }
+    }
+
+    #[test]
+    fn test_crank_response_legacy() {
+        // A legacy response without a next instruction.
+        let response = CrankResponse::try_from_return_data(
+            &Option::<InstructionData>::None.try_to_vec().unwrap(),
+        )
+        .unwrap();
+        assert!(response.next_instructions.is_empty());
+        assert!(response.close_to.is_none());
+        assert!(response.kickoff_instructions.is_none());
+        assert!(!response.pause);
+        assert!(response.trigger.is_none());
+
+        // A legacy response with a next instruction.
+        let next_instruction = instruction(vec![7, 8, 9]);
+        let response = CrankResponse::try_from_return_data(
+            &Some(next_instruction.clone()).try_to_vec().unwrap(),
+        )
+        .unwrap();
+        assert_eq!(
+            response.next_instructions,
+            vec![QueueInstruction::Inline(next_instruction)]
+        );
+    }
+
+    #[test]
+    fn test_crank_response_invalid() {
+        assert!(CrankResponse::try_from_return_data(&[]).is_err());
+        assert!(CrankResponse::try_from_return_data(&[3]).is_err());
+        assert!(CrankResponse::try_from_return_data(&[CRANK_RESPONSE_VERSION]).is_err());
+        assert!(CrankResponse::try_from_return_data(&[1]).is_err());
+    }
+
+    #[test]
+    fn test_count_exec_in_window_slots() {
+        let exec_rate_limit = Some(ExecRateLimit {
+            max_executions: 3,
+            window: ExecWindow::Slots { slots: 10 },
+        });
+
+        // Executions are counted against the current window until it is full.
+        let queue_a = queue(Some(exec_context(2, 100)), exec_rate_limit);
+        assert_eq!(
+            queue_a.count_exec_in_window(&clock(105, 0)).unwrap(),
+            (3, 100)
+        );
+        let queue_b = queue(Some(exec_context(3, 100)), exec_rate_limit);
+        assert!(queue_b.count_exec_in_window(&clock(109, 0)).is_err());
+
+        // A new window starts once the current one has elapsed.
+        assert_eq!(
+            queue_b.count_exec_in_window(&clock(110, 0)).unwrap(),
+            (1, 110)
+        );
+    }
+
+    #[test]
+    fn test_count_exec_in_window_seconds() {
+        let exec_rate_limit = Some(ExecRateLimit {
+            max_executions: 2,
+            window: ExecWindow::Seconds { seconds: 60 },
+        });
+        let queue = queue(Some(exec_context(2, 1_000)), exec_rate_limit);
+
+        // Only unix time is considered, not slots.
+        assert!(queue
+            .count_exec_in_window(&clock(1_000_000, 1_059))
+            .is_err());
+        assert_eq!(
+            queue.count_exec_in_window(&clock(0, 1_060)).unwrap(),
+            (1, 1_060)
+        );
+    }
+
+    #[test]
+    fn test_count_exec_in_window_without_context() {
+        let exec_rate_limit = Some(ExecRateLimit {
+            max_executions: 1,
+            window: ExecWindow::Slots { slots: 10 },
+        });
+
+        // The first execution starts the first window.
+        let queue_a = queue(None, exec_rate_limit);
+        assert_eq!(
+            queue_a.count_exec_in_window(&clock(100, 0)).unwrap(),
+            (1, 100)
+        );
+
+        // Clearing the trigger context, as a trigger swap does, keeps the window.
+        let queue_b = queue(
+            Some(ExecContext {
+                trigger_context: None,
+                ..exec_context(1, 100)
+            }),
+            exec_rate_limit,
+        );
+        assert!(queue_b.trigger_context().is_none());
+        assert!(queue_b.count_exec_in_window(&clock(105, 0)).is_err());
+    }
+
+    #[test]
+    fn test_count_exec_in_window_without_limit() {
+        let queue = queue(Some(exec_context(100, 0)), None);
+        assert_eq!(queue.count_exec_in_window(&clock(0, 0)).unwrap(), (0, 0));
+    }
+
+    #[test]
+    fn test_fail_pauses_without_error_instruction() {
+        let mut queue = queue(None, None);
+        for slot in 1..=DEFAULT_MAX_RETRIES {
+            queue.fail(slot, None);
+            assert!(!queue.paused);
+        }
+        queue.fail(100, None);
+        assert!(queue.paused);
+        assert_eq!(queue.consecutive_failures, DEFAULT_MAX_RETRIES + 1);
+        assert_eq!(queue.last_failure_at, Some(100));
+    }
+
+    #[test]
+    fn test_fail_schedules_error_instruction() {
+        let error_instruction = QueueInstruction::Inline(instruction(vec![1]));
+        let mut queue = queue(None, None);
+        queue.consecutive_failures = DEFAULT_MAX_RETRIES;
+        queue.error_instruction = Some(error_instruction.clone());
+
+        // Exhausting the retries of a kickoff starts a thread to run the error instruction in.
+        queue.fail(100, Some(exec_context(1, 100)));
+        assert!(!queue.paused);
+        assert_eq!(queue.next_instructions, vec![error_instruction]);
+        assert_eq!(queue.exec_context, Some(exec_context(1, 100)));
+
+        // If the error instruction fails too, the failure is recorded and the queue is paused.
+        queue.fail(200, None);
+        assert!(queue.paused);
+        assert_eq!(queue.last_failure_at, Some(200));
+    }
+
+    #[test]
+    fn test_verify_failed_attempt() {
+        let mut queue = queue(
+            Some(ExecContext {
+                last_crank_at: 100,
+                ..exec_context(0, 0)
+            }),
+            None,
+        );
+
+        // The attempt must be recent and not followed by a successful crank.
+        assert!(queue.verify_failed_attempt(100, 110).is_ok());
+        assert!(queue.verify_failed_attempt(99, 110).is_err());
+        assert!(queue.verify_failed_attempt(111, 110).is_err());
+        assert!(queue
+            .verify_failed_attempt(100, 100 + MAX_FAILED_ATTEMPT_AGE + 1)
+            .is_err());
+
+        // The attempt must be made once the retry backoff has elapsed.
+        queue.consecutive_failures = 2;
+        queue.last_failure_at = Some(120);
+        assert!(queue.verify_failed_attempt(123, 130).is_err());
+        assert!(queue.verify_failed_attempt(124, 130).is_ok());
+    }
+
+    #[test]
+    fn test_predicate_cannot_debit_queue() {
+        program_stubs::set_syscall_stubs(Box::new(TestSyscallStubs));
+
+        // A predicate which tries to transfer the queue's lamports to the worker.
+        let mut queue_data = queue(None, None);
+        let (queue_pubkey, bump) = Pubkey::find_program_address(
+            &[
+                SEED_QUEUE,
+                queue_data.authority.as_ref(),
+                queue_data.id.as_bytes(),
+            ],
+            &crate::ID,
+        );
+        let worker_pubkey = Pubkey::new_unique();
+        let predicate = InstructionData::from(system_instruction::transfer(
+            &queue_pubkey,
+            &worker_pubkey,
+            1_000_000,
+        ));
+        queue_data.trigger = Trigger::Condition {
+            predicate: predicate.clone().into(),
+        };
+
+        let mut queue_data = account_data(&queue_data);
+        let (mut queue_lamports, mut worker_lamports) = (2_000_000, 0);
+        let queue_info = AccountInfo::new(
+            &queue_pubkey,
+            false,
+            true,
+            &mut queue_lamports,
+            &mut queue_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let worker_info = AccountInfo::new(
+            &worker_pubkey,
+            true,
+            true,
+            &mut worker_lamports,
+            &mut [],
+            &system_program::ID,
+            false,
+            0,
+        );
+        let account_infos = [queue_info.clone(), worker_info.clone()];
+        let queue = Account::<Queue>::try_from(&queue_info).unwrap();
+        let worker = Signer::try_from(&worker_info).unwrap();
+
+        // The predicate is invoked without the queue's signature, so the transfer is refused.
+        assert!(queue.verify_trigger(&account_infos, None, &worker).is_err());
+
+        // The queue's instructions are still invoked with its signature.
+        assert!(queue
+            .invoke(&predicate, &account_infos, bump, &worker)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_grow_and_close_sponsored_queue() {
+        let sponsored_lamports = 1_000_000;
+        let (queue_pubkey, mut queue_data, mut queue_lamports, funding_pubkey, mut funding_data) =
+            grown_sponsored_queue(sponsored_lamports);
+        let grown_minimum_balance = Rent::default().minimum_balance(queue_data.len());
+        let (mut funding_lamports, mut close_to_lamports) = (0, 0);
+        let close_to_pubkey = Pubkey::new_unique();
+        let queue_info = AccountInfo::new(
+            &queue_pubkey,
+            false,
+            true,
+            &mut queue_lamports,
+            &mut queue_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let funding_info = AccountInfo::new(
+            &funding_pubkey,
+            false,
+            true,
+            &mut funding_lamports,
+            &mut funding_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let close_to_info = AccountInfo::new(
+            &close_to_pubkey,
+            false,
+            true,
+            &mut close_to_lamports,
+            &mut [],
+            &system_program::ID,
+            false,
+            0,
+        );
+        let mut queue = Account::<Queue>::try_from(&queue_info).unwrap();
+
+        // The added rent would come out of the sponsored lamports, so the authority must pay it.
+        let rent_shortfall = queue.rent_shortfall().unwrap();
+        assert!(rent_shortfall > 0);
+        assert!(queue.spendable_balance().unwrap() < sponsored_lamports);
+        **queue_info.try_borrow_mut_lamports().unwrap() += rent_shortfall;
+        assert_eq!(queue.rent_shortfall().unwrap(), 0);
+        assert_eq!(queue.spendable_balance().unwrap(), sponsored_lamports);
+
+        // Closing the queue holds back the sponsored lamports and returns only the rent.
+        close_queue(
+            std::slice::from_ref(&funding_info),
+            255,
+            close_to_info.clone(),
+            &mut queue,
+        )
+        .unwrap();
+        assert_eq!(funding_info.lamports(), sponsored_lamports);
+        assert_eq!(close_to_info.lamports(), grown_minimum_balance);
+        assert_eq!(queue_info.lamports(), 0);
+        let funding = Account::<Funding>::try_from(&funding_info).unwrap();
+        assert!(funding.is_closed);
+        assert_eq!(funding.reclaimable, sponsored_lamports);
+    }
+
+    #[test]
+    fn test_close_sponsored_queue_below_rent() {
+        let sponsored_lamports = 1_000_000;
+        let (queue_pubkey, mut queue_data, mut queue_lamports, funding_pubkey, mut funding_data) =
+            grown_sponsored_queue(sponsored_lamports);
+        let total_lamports = queue_lamports;
+        let (mut funding_lamports, mut close_to_lamports) = (0, 0);
+        let close_to_pubkey = Pubkey::new_unique();
+        let queue_info = AccountInfo::new(
+            &queue_pubkey,
+            false,
+            true,
+            &mut queue_lamports,
+            &mut queue_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let funding_info = AccountInfo::new(
+            &funding_pubkey,
+            false,
+            true,
+            &mut funding_lamports,
+            &mut funding_data,
+            &crate::ID,
+            false,
+            0,
+        );
+        let close_to_info = AccountInfo::new(
+            &close_to_pubkey,
+            false,
+            true,
+            &mut close_to_lamports,
+            &mut [],
+            &system_program::ID,
+            false,
+            0,
+        );
+        let mut queue = Account::<Queue>::try_from(&queue_info).unwrap();
+
+        // Even if the sponsored lamports were spent on rent, they are escrowed in full when the queue is closed.
+        close_queue(
+            std::slice::from_ref(&funding_info),
+            255,
+            close_to_info.clone(),
+            &mut queue,
+        )
+        .unwrap();
+        assert_eq!(funding_info.lamports(), sponsored_lamports);
+        assert_eq!(
+            close_to_info.lamports(),
+            total_lamports - sponsored_lamports
+        );
+    }
 }
@@ -1,11 +1,19 @@
 //! Utility functions to make it easier to build programs with Clockwork.
 
 use anchor_lang::{prelude::Pubkey, solana_program};
+use chrono::{DateTime, FixedOffset, NaiveDateTime, Utc};
+use chrono_tz::Tz;
+use clockwork_cron::Schedule;
 use static_pubkey::static_pubkey;
+use std::str::FromStr;
 
 /// The stand-in pubkey for delegating a payer address to a worker. All workers are re-imbursed by the user for lamports spent during this delegation.
 pub static PAYER_PUBKEY: Pubkey = static_pubkey!("C1ockworkPayer11111111111111111111111111111");
 
+/// The program ID of Solana's compute budget program.
+pub static COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    static_pubkey!("ComputeBudget111111111111111111111111111111");
+
 /// The sighash of a named instruction in an Anchor program.
 pub fn anchor_sighash(name: &str) -> [u8; 8] {
     let namespace = "global";
@@ -16,3 +24,157 @@ pub fn anchor_sighash(name: &str) -> [u8; 8] {
     );
     sighash
 }
+
+/// The data hash of the bytes watched by an account trigger.
+///
+/// The watched range starts at `offset` and spans `size` bytes, defaulting to the full account data.
+/// Ranges that extend past the end of the data are clamped to its length. The hash is the first 8 bytes
+/// of the range's sha256 digest, read as a little-endian `u64`. Workers and the queue program must agree
+/// on this value bit-for-bit, so it must never depend on the Rust toolchain or platform.
+pub fn account_data_hash(data: &[u8], offset: Option<u64>, size: Option<u64>) -> u64 {
+    let start = (offset.unwrap_or(0) as usize).min(data.len());
+    let end = match size {
+        None => data.len(),
+        Some(size) => start.saturating_add(size as usize).min(data.len()),
+    };
+    let digest = anchor_lang::solana_program::hash::hash(&data[start..end]).to_bytes();
+    let mut data_hash = [0u8; 8];
+    data_hash.copy_from_slice(&digest[..8]);
+    u64::from_le_bytes(data_hash)
+}
+
+/// The timezone a cron schedule is evaluated in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CronTimezone {
+    /// A fixed offset from UTC, written as `+HH:MM` or `-HH:MM`.
+    Fixed(FixedOffset),
+
+    /// A named IANA timezone, such as `America/New_York`. Named timezones observe daylight saving time. Only the
+    /// timezones kept by the `CHRONO_TZ_TIMEZONE_FILTER` in `.cargo/config.toml` are compiled in.
+    Named(Tz),
+}
+
+impl FromStr for CronTimezone {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let sign = match s.chars().next() {
+            Some('+') => 1,
+            Some('-') => -1,
+            _ => return s.parse::<Tz>().map(CronTimezone::Named).map_err(|_err| ()),
+        };
+        let (hours, minutes) = s[1..].split_once(':').ok_or(())?;
+        if hours.len() != 2 || minutes.len() != 2 {
+            return Err(());
+        }
+        let hours = hours.parse::<i32>().map_err(|_err| ())?;
+        let minutes = minutes.parse::<i32>().map_err(|_err| ())?;
+        if minutes >= 60 {
+            return Err(());
+        }
+        FixedOffset::east_opt(sign * (hours * 3600 + minutes * 60))
+            .map(CronTimezone::Fixed)
+            .ok_or(())
+    }
+}
+
+/// The first moment in a cron schedule after a unix timestamp.
+///
+/// The schedule is evaluated in the given timezone (UTC if none). Local times skipped by a DST transition never
+/// occur, and local times repeated by a DST transition occur only once, at their first occurrence. Workers and the
+/// queue program must agree on this value, so both must use this function.
+pub fn next_cron_timestamp(after: i64, schedule: &str, timezone: Option<&str>) -> Option<i64> {
+    let schedule = Schedule::from_str(schedule).ok()?;
+    let after = DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp_opt(after, 0)?, Utc);
+    match timezone.map(CronTimezone::from_str).transpose().ok()? {
+        None => schedule.next_after(&after).map(|dt| dt.timestamp()),
+        Some(CronTimezone::Fixed(tz)) => schedule
+            .next_after(&after.with_timezone(&tz))
+            .map(|dt| dt.timestamp()),
+        Some(CronTimezone::Named(tz)) => schedule
+            .next_after(&after.with_timezone(&tz))
+            .map(|dt| dt.timestamp()),
+    }
+}
+
+/// The last moment in a cron schedule before a unix timestamp. See `next_cron_timestamp` for how timezones are handled.
+pub fn prev_cron_timestamp(before: i64, schedule: &str, timezone: Option<&str>) -> Option<i64> {
+    let schedule = Schedule::from_str(schedule).ok()?;
+    let before = DateTime::<Utc>::from_utc(NaiveDateTime::from_timestamp_opt(before, 0)?, Utc);
+    match timezone.map(CronTimezone::from_str).transpose().ok()? {
+        None => schedule.prev_before(&before).map(|dt| dt.timestamp()),
+        Some(CronTimezone::Fixed(tz)) => schedule
+            .prev_before(&before.with_timezone(&tz))
+            .map(|dt| dt.timestamp()),
+        Some(CronTimezone::Named(tz)) => schedule
+            .prev_before(&before.with_timezone(&tz))
+            .map(|dt| dt.timestamp()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_account_data_hash() {
+        let data = [1u8, 2, 3, 4, 5, 6, 7, 8];
+
+        // Hashes only the watched bytes.
+        let watched = account_data_hash(&data, Some(2), Some(3));
+        assert_eq!(watched, account_data_hash(&data[2..5], None, None));
+        let mut changed = data;
+        changed[0] = 0;
+        changed[7] = 0;
+        assert_eq!(watched, account_data_hash(&changed, Some(2), Some(3)));
+        changed[3] = 0;
+        assert_ne!(watched, account_data_hash(&changed, Some(2), Some(3)));
+
+        // Watches through the last byte if no size is given.
+        assert_eq!(
+            account_data_hash(&data, Some(6), None),
+            account_data_hash(&data[6..], None, None)
+        );
+
+        // Clamps windows which run past the end of the data.
+        assert_eq!(
+            account_data_hash(&data, Some(6), Some(100)),
+            account_data_hash(&data[6..], None, None)
+        );
+        assert_eq!(
+            account_data_hash(&data, Some(100), None),
+            account_data_hash(&[], None, None)
+        );
+    }
+
+    #[test]
+    fn test_cron_timezone_from_str() {
+        assert_eq!(
+            CronTimezone::from_str("+05:30"),
+            Ok(CronTimezone::Fixed(
+                FixedOffset::east_opt(5 * 3600 + 30 * 60).unwrap()
+            ))
+        );
+        assert_eq!(
+            CronTimezone::from_str("-08:00"),
+            Ok(CronTimezone::Fixed(
+                FixedOffset::west_opt(8 * 3600).unwrap()
+            ))
+        );
+        assert_eq!(
+            CronTimezone::from_str("America/New_York"),
+            Ok(CronTimezone::Named(Tz::America__New_York))
+        );
+
+        // Rejects malformed offsets, out of range offsets, and unknown names.
+        assert!(CronTimezone::from_str("+5:30").is_err());
+        assert!(CronTimezone::from_str("+05:60").is_err());
+        assert!(CronTimezone::from_str("+24:00").is_err());
+        assert!(CronTimezone::from_str("+05").is_err());
+        assert!(CronTimezone::from_str("Mars/Olympus_Mons").is_err());
+        assert!(CronTimezone::from_str("").is_err());
+
+        // Rejects names which are not compiled into the program.
+        assert!(CronTimezone::from_str("Asia/Kathmandu").is_err());
+    }
+}